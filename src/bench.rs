@@ -0,0 +1,172 @@
+use std::{
+	fmt::Display,
+	time::{Duration, Instant},
+};
+
+use crate::{InputError, days::Day, fixtures::Fixture};
+
+pub const DEFAULT_FACTORS: [usize; 4] = [1, 2, 4, 8];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+	pub factors: Vec<usize>,
+	/* Every measurement is repeated until it took at least this long */
+	pub min_time: Duration,
+	pub max_iterations: usize,
+}
+
+impl Default for Options {
+	fn default() -> Self {
+		Self {
+			factors: DEFAULT_FACTORS.to_vec(),
+			min_time: Duration::from_millis(200),
+			max_iterations: 1000,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchResult {
+	pub day: u8,
+	pub fixture: String,
+	pub factor: usize,
+	pub bytes: usize,
+	pub iterations: usize,
+	pub parse: Duration,
+	pub solve: Duration,
+}
+
+impl BenchResult {
+	pub const HEADER: &str =
+		"day  fixture         scale      input      parse      solve   throughput";
+
+	pub fn total(&self) -> Duration {
+		self.parse + self.solve
+	}
+
+	pub fn bytes_per_second(&self) -> f64 {
+		self.bytes as f64 / self.total().as_secs_f64().max(f64::MIN_POSITIVE)
+	}
+}
+
+impl Display for BenchResult {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{:02}   {:<15} {:>5}x {:>10} {:>10} {:>10} {:>10}/s",
+			self.day,
+			self.fixture,
+			self.factor,
+			format_bytes(self.bytes as f64),
+			format!("{:.2?}", self.parse),
+			format!("{:.2?}", self.solve),
+			format_bytes(self.bytes_per_second()),
+		)
+	}
+}
+
+/* Benchmarks `day` on the fixture, and on the fixture scaled up by each of
+ * the factors. Factors the day can't scale to are skipped. */
+pub fn bench(
+	day: &Day,
+	fixture: &Fixture,
+	options: &Options,
+) -> Result<Vec<BenchResult>, InputError> {
+	let mut results = Vec::new();
+
+	for &factor in &options.factors {
+		let input = if factor == 1 {
+			fixture.input.clone()
+		} else {
+			match (day.scale)(&fixture.input, factor) {
+				Some(input) => input,
+				None => continue,
+			}
+		};
+
+		let mut parse = Vec::new();
+		let mut solve = Vec::new();
+		let start = Instant::now();
+
+		while parse.is_empty()
+			|| (start.elapsed() < options.min_time && parse.len() < options.max_iterations)
+		{
+			let measurement = (day.measure)(&input)?;
+			parse.push(measurement.parse);
+			solve.push(measurement.solve);
+		}
+
+		results.push(BenchResult {
+			day: day.number,
+			fixture: fixture.name.clone(),
+			factor,
+			bytes: input.len(),
+			iterations: parse.len(),
+			parse: median(&mut parse),
+			solve: median(&mut solve),
+		});
+	}
+
+	Ok(results)
+}
+
+fn median(durations: &mut [Duration]) -> Duration {
+	durations.sort_unstable();
+	durations[durations.len() / 2]
+}
+
+fn format_bytes(bytes: f64) -> String {
+	const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+	let mut value = bytes;
+	let mut unit = 0;
+	while value >= 1024.0 && unit < UNITS.len() - 1 {
+		value /= 1024.0;
+		unit += 1;
+	}
+
+	if unit == 0 {
+		format!("{value:.0} {}", UNITS[unit])
+	} else {
+		format!("{value:.1} {}", UNITS[unit])
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::days;
+
+	#[test]
+	fn test_format_bytes() {
+		assert_eq!(format_bytes(12.0), "12 B");
+		assert_eq!(format_bytes(1536.0), "1.5 KiB");
+		assert_eq!(format_bytes(3.0 * 1024.0 * 1024.0), "3.0 MiB");
+	}
+
+	#[test]
+	fn test_bench_scales_input() {
+		let fixture = Fixture {
+			day: 1,
+			name: "example".to_string(),
+			path: Default::default(),
+			input: "Short message.\n".to_string(),
+			answer: None,
+		};
+		let options = Options {
+			factors: vec![1, 3],
+			min_time: Duration::ZERO,
+			max_iterations: 1,
+		};
+
+		let results = bench(&days::get(1).unwrap(), &fixture, &options).unwrap();
+
+		assert_eq!(
+			results
+				.iter()
+				.map(|result| (result.factor, result.bytes, result.iterations))
+				.collect::<Vec<_>>(),
+			vec![(1, 15, 1), (3, 45, 1)]
+		);
+	}
+}
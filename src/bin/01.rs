@@ -1,26 +1,27 @@
-use std::io::stdin;
+use std::{
+	io::{read_to_string, stdin},
+	process::ExitCode,
+};
 
-fn main() {
-	let total_cost = stdin()
-		.lines()
-		.map(Result::unwrap)
-		.map(|line| cost(&line))
-		.sum::<u32>();
+use i18n_puzzles::{Puzzle as _, days::Day01};
 
-	println!("Total cost: {total_cost}");
-}
-
-fn cost(input: &str) -> u32 {
-	const SMS_THRESHOLD: usize = 160;
-	const TWEET_THRESHOLD: usize = 140;
-
-	let bytes = input.len();
-	let characters = input.chars().count();
+fn main() -> ExitCode {
+	let input = match read_to_string(stdin()) {
+		Ok(input) => input,
+		Err(err) => {
+			eprintln!("Could not read the input: {err}");
+			return ExitCode::FAILURE;
+		}
+	};
 
-	match (bytes <= SMS_THRESHOLD, characters <= TWEET_THRESHOLD) {
-		(true, true) => 13,
-		(true, false) => 11,
-		(false, true) => 7,
-		(false, false) => 0,
+	match Day01::run(&input) {
+		Ok(answer) => {
+			println!("Total cost: {answer}");
+			ExitCode::SUCCESS
+		}
+		Err(err) => {
+			eprintln!("{err}");
+			ExitCode::FAILURE
+		}
 	}
 }
@@ -1,18 +1,27 @@
-use std::{io::stdin, str::FromStr as _};
+use std::{
+	io::{read_to_string, stdin},
+	process::ExitCode,
+};
 
-use chrono::{DateTime, Utc};
-use itertools::Itertools;
+use i18n_puzzles::{Puzzle as _, days::Day02};
 
-fn main() {
-	let answer = stdin()
-		.lines()
-		.map(Result::unwrap)
-		.map(|str| DateTime::<Utc>::from_str(&str).unwrap())
-		.counts()
-		.into_iter()
-		.filter_map(|(instant, count)| (count >= 4).then_some(instant))
-		.exactly_one()
-		.expect("Expected only one instant that occurs four or more times in the input");
+fn main() -> ExitCode {
+	let input = match read_to_string(stdin()) {
+		Ok(input) => input,
+		Err(err) => {
+			eprintln!("Could not read the input: {err}");
+			return ExitCode::FAILURE;
+		}
+	};
 
-	println!("{}", answer.fixed_offset().to_rfc3339());
+	match Day02::run(&input) {
+		Ok(answer) => {
+			println!("{answer}");
+			ExitCode::SUCCESS
+		}
+		Err(err) => {
+			eprintln!("{err}");
+			ExitCode::FAILURE
+		}
+	}
 }
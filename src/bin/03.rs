@@ -1,20 +1,27 @@
-use std::io::stdin;
+use std::{
+	io::{read_to_string, stdin},
+	process::ExitCode,
+};
 
-fn main() {
-	println!(
-		"Answer: {}",
-		stdin()
-			.lines()
-			.map(Result::unwrap)
-			.filter(|password| is_valid(password))
-			.count()
-	);
-}
+use i18n_puzzles::{Puzzle as _, days::Day03};
+
+fn main() -> ExitCode {
+	let input = match read_to_string(stdin()) {
+		Ok(input) => input,
+		Err(err) => {
+			eprintln!("Could not read the input: {err}");
+			return ExitCode::FAILURE;
+		}
+	};
 
-fn is_valid(password: &str) -> bool {
-	(4..=12).contains(&password.chars().count())
-		&& password.chars().any(|ch| ch.is_ascii_digit())
-		&& password.chars().any(|ch| ch.is_lowercase())
-		&& password.chars().any(|ch| ch.is_uppercase())
-		&& !password.is_ascii()
+	match Day03::run(&input) {
+		Ok(answer) => {
+			println!("Answer: {answer}");
+			ExitCode::SUCCESS
+		}
+		Err(err) => {
+			eprintln!("{err}");
+			ExitCode::FAILURE
+		}
+	}
 }
@@ -1,21 +1,27 @@
-use std::io::stdin;
+use std::{
+	io::{read_to_string, stdin},
+	process::ExitCode,
+};
 
-const PILE_OF_POO: char = '💩';
+use i18n_puzzles::{Puzzle as _, days::Day05};
 
-fn main() {
-	let lines = stdin().lines().map(Result::unwrap).collect::<Vec<_>>();
+fn main() -> ExitCode {
+	let input = match read_to_string(stdin()) {
+		Ok(input) => input,
+		Err(err) => {
+			eprintln!("Could not read the input: {err}");
+			return ExitCode::FAILURE;
+		}
+	};
 
-	let line_length = lines[0].chars().count();
-
-	let times_stepped_in_poo = lines
-		.iter()
-		.enumerate()
-		.map(|(index, line)| {
-			let char_index = index * 2 % line_length;
-			line.chars().nth(char_index).unwrap()
-		})
-		.filter(|&char| char == PILE_OF_POO)
-		.count();
-
-	println!("Stepped in poo {times_stepped_in_poo} times");
+	match Day05::run(&input) {
+		Ok(answer) => {
+			println!("Stepped in poo {answer} times");
+			ExitCode::SUCCESS
+		}
+		Err(err) => {
+			eprintln!("{err}");
+			ExitCode::FAILURE
+		}
+	}
 }
@@ -1,60 +1,27 @@
-use std::{collections::BTreeMap, io::stdin};
-
-use rayon::iter::{IntoParallelIterator, ParallelIterator as _};
-use unicode_normalization::UnicodeNormalization;
-
-fn main() {
-	let mut lines = stdin().lines().map(Result::unwrap);
-
-	let passwords: BTreeMap<String, String> = (&mut lines)
-		.take_while(|line| !line.is_empty())
-		.map(|line| {
-			let (name, pw) = line.split_once(' ').unwrap();
-			(name.to_string(), pw.to_string())
-		})
-		.collect();
-
-	let valid_attempts = lines
-		.filter(|line| {
-			let (name, pw) = line.split_once(' ').unwrap();
-			let Some(stored) = passwords.get(name) else {
-				return false;
-			};
-
-			possible_representations(pw)
-				.into_par_iter()
-				.any(|repr| bcrypt::verify(repr, stored).unwrap())
-		})
-		.count();
-
-	println!("Valid attempts: {valid_attempts}");
-}
-
-fn possible_representations(password: &str) -> Vec<String> {
-	fn possible_representations(acc: &mut Vec<String>, mut prefix: String, remaining: &[char]) {
-		match remaining {
-			[] => {
-				acc.push(prefix);
-			}
-			[ch, remaining @ ..] => {
-				let mut decomposed = ch.nfd();
-				if let (Some(fst), Some(snd)) = (decomposed.next(), decomposed.next()) {
-					let mut prefix = prefix.clone();
-					prefix.push(fst);
-					prefix.push(snd);
-					possible_representations(acc, prefix, remaining);
-				}
+use std::{
+	io::{read_to_string, stdin},
+	process::ExitCode,
+};
+
+use i18n_puzzles::{Puzzle as _, days::Day10};
+
+fn main() -> ExitCode {
+	let input = match read_to_string(stdin()) {
+		Ok(input) => input,
+		Err(err) => {
+			eprintln!("Could not read the input: {err}");
+			return ExitCode::FAILURE;
+		}
+	};
 
-				prefix.push(*ch);
-				possible_representations(acc, prefix, remaining);
-			}
+	match Day10::run(&input) {
+		Ok(answer) => {
+			println!("Valid attempts: {answer}");
+			ExitCode::SUCCESS
+		}
+		Err(err) => {
+			eprintln!("{err}");
+			ExitCode::FAILURE
 		}
 	}
-
-	let chars = password.nfc().collect::<Vec<_>>();
-	let mut acc = Vec::new();
-
-	possible_representations(&mut acc, String::new(), &chars[..]);
-
-	acc
 }
@@ -0,0 +1,318 @@
+use std::{ops::Range, str::FromStr};
+
+use crate::{
+	InputError,
+	error::{Line, numbered_lines},
+	utf8,
+};
+
+/* How bytes are written down in an input */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	/* Pairs of hexadecimal digits, optionally separated by whitespace and
+	 * prefixed by 0x */
+	Hex,
+	/* Standard base64, padded or not, with line breaks anywhere */
+	Base64,
+	/* The bytes themselves */
+	Binary,
+}
+
+impl Format {
+	pub const ALL: [Format; 3] = [Format::Hex, Format::Base64, Format::Binary];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Format::Hex => "hex",
+			Format::Base64 => "base64",
+			Format::Binary => "binary",
+		}
+	}
+
+	/* Hex digits are base64 digits too, so hex is preferred. Anything that's
+	 * neither is taken as binary. */
+	pub fn guess(input: &[u8]) -> Format {
+		[Format::Hex, Format::Base64]
+			.into_iter()
+			.find(|&format| parse(input, format).is_ok())
+			.unwrap_or(Format::Binary)
+	}
+}
+
+impl FromStr for Format {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Format::ALL
+			.into_iter()
+			.find(|format| format.name() == s)
+			.ok_or_else(|| format!("Unknown format '{s}'"))
+	}
+}
+
+/* Parses a whole input. Hex bytes can't be split over lines, base64 groups
+ * can. */
+pub fn parse(input: &[u8], format: Format) -> Result<Vec<u8>, InputError> {
+	if format == Format::Binary {
+		return Ok(input.to_vec());
+	}
+
+	let text = std::str::from_utf8(input).map_err(|_| {
+		let offset = utf8::errors(input).next().unwrap().offset;
+		let before = &input[..offset];
+		let start = before
+			.iter()
+			.rposition(|&b| b == b'\n')
+			.map_or(0, |i| i + 1);
+
+		InputError {
+			line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+			column: String::from_utf8_lossy(&before[start..]).chars().count() + 1,
+			text: format!("{:02x}", input[offset]),
+			message: format!("Expected {} text", format.name()),
+		}
+	})?;
+
+	match format {
+		Format::Hex => {
+			let mut bytes = Vec::new();
+			for line in numbered_lines(text) {
+				bytes.extend(parse_hex(line)?);
+			}
+			Ok(bytes)
+		}
+		Format::Base64 => {
+			let mut decoder = Base64::default();
+			let mut last = None;
+			for line in numbered_lines(text) {
+				decoder.push(line)?;
+				last = Some(line);
+			}
+			match last {
+				Some(line) => decoder.finish(line),
+				None => Ok(Vec::new()),
+			}
+		}
+		Format::Binary => unreachable!(),
+	}
+}
+
+pub fn parse_hex(line: Line) -> Result<Vec<u8>, InputError> {
+	parse_hex_spans(line).map(|(bytes, _)| bytes)
+}
+
+/* Like `parse_hex`, but also returns where in the line each byte is written,
+ * to report problems with the bytes at the right column */
+pub fn parse_hex_spans(line: Line) -> Result<(Vec<u8>, Vec<Range<usize>>), InputError> {
+	let text = line.text;
+	let mut bytes = Vec::with_capacity(text.len() / 2);
+	let mut spans = Vec::with_capacity(text.len() / 2);
+
+	for (start, word) in words(text) {
+		let (start, digits) = match word.get(..2) {
+			Some("0x" | "0X") => (start + 2, &word[2..]),
+			_ => (start, word),
+		};
+
+		if digits.is_empty() {
+			return Err(line.error_at_offset(
+				start - 2,
+				word,
+				"Expected hexadecimal digits after 0x",
+			));
+		}
+		if let Some((offset, ch)) = digits
+			.char_indices()
+			.find(|(_, ch)| !ch.is_ascii_hexdigit())
+		{
+			let ch = &digits[offset..offset + ch.len_utf8()];
+			return Err(line.error_at_offset(start + offset, ch, "Expected a hexadecimal digit"));
+		}
+		if digits.len() % 2 != 0 {
+			let last = start + digits.len() - 1;
+			return Err(line.error_at_offset(
+				last,
+				&text[last..last + 1],
+				"Expected pairs of hexadecimal digits",
+			));
+		}
+
+		for i in (0..digits.len()).step_by(2) {
+			bytes.push(u8::from_str_radix(&digits[i..i + 2], 16).unwrap());
+			spans.push(start + i..start + i + 2);
+		}
+	}
+
+	Ok((bytes, spans))
+}
+
+pub fn parse_base64(line: Line) -> Result<Vec<u8>, InputError> {
+	let mut decoder = Base64::default();
+	decoder.push(line)?;
+	decoder.finish(line)
+}
+
+/* The words of `text` with their offsets */
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+	text.split_whitespace()
+		.map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+fn base64_value(ch: char) -> Option<u32> {
+	match ch {
+		'A'..='Z' => Some(ch as u32 - 'A' as u32),
+		'a'..='z' => Some(ch as u32 - 'a' as u32 + 26),
+		'0'..='9' => Some(ch as u32 - '0' as u32 + 52),
+		'+' => Some(62),
+		'/' => Some(63),
+		_ => None,
+	}
+}
+
+/* Decodes base64 a line at a time, as a group of four digits can be split
+ * over lines */
+#[derive(Debug, Default)]
+struct Base64 {
+	bytes: Vec<u8>,
+	bits: u32,
+	len: u32,
+	digits: usize,
+	padding: usize,
+}
+
+impl Base64 {
+	fn push(&mut self, line: Line) -> Result<(), InputError> {
+		for (offset, ch) in line.text.char_indices() {
+			let at = &line.text[offset..offset + ch.len_utf8()];
+
+			match ch {
+				ch if ch.is_whitespace() => {}
+				'=' if self.digits % 4 >= 2 && (self.digits + self.padding) % 4 != 0 => {
+					self.padding += 1;
+				}
+				'=' => return Err(line.error_at_offset(offset, at, "Unexpected padding")),
+				_ if self.padding > 0 => {
+					return Err(line.error_at_offset(offset, at, "Unexpected data after padding"));
+				}
+				ch => {
+					let value = base64_value(ch).ok_or_else(|| {
+						line.error_at_offset(offset, at, "Expected a base64 digit")
+					})?;
+
+					self.bits = (self.bits << 6) | value;
+					self.len += 6;
+					self.digits += 1;
+					if self.len >= 8 {
+						self.len -= 8;
+						self.bytes.push((self.bits >> self.len) as u8);
+						self.bits &= (1 << self.len) - 1;
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/* `line` is the last line, to report missing digits at the end of */
+	fn finish(self, line: Line) -> Result<Vec<u8>, InputError> {
+		if self.digits % 4 == 1 {
+			return Err(line.end_error("another base64 digit"));
+		}
+		if self.padding > 0 && (self.digits + self.padding) % 4 != 0 {
+			return Err(line.end_error("padding up to a group of four digits"));
+		}
+
+		Ok(self.bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn line(text: &str) -> Line<'_> {
+		Line { number: 1, text }
+	}
+
+	#[test]
+	fn test_parse_hex() {
+		assert_eq!(parse_hex(line("e29594")), Ok(vec![0xe2, 0x95, 0x94]));
+		assert_eq!(parse_hex(line(" E2 95\t94 ")), Ok(vec![0xe2, 0x95, 0x94]));
+		assert_eq!(parse_hex(line("0xe2 0X9594")), Ok(vec![0xe2, 0x95, 0x94]));
+		assert_eq!(parse_hex(line("")), Ok(vec![]));
+
+		let (_, spans) = parse_hex_spans(line("0xe2 9594")).unwrap();
+		assert_eq!(spans, [2..4, 5..7, 7..9]);
+	}
+
+	#[test]
+	fn test_parse_hex_errors() {
+		let err = parse_hex(line("e2 9g94")).unwrap_err();
+		assert_eq!((err.column, err.text.as_str()), (5, "g"));
+		assert_eq!(err.message, "Expected a hexadecimal digit");
+
+		let err = parse_hex(line("e2 959 4")).unwrap_err();
+		assert_eq!((err.column, err.text.as_str()), (6, "9"));
+		assert_eq!(err.message, "Expected pairs of hexadecimal digits");
+
+		let err = parse_hex(line("e2 0x")).unwrap_err();
+		assert_eq!(err.column, 4);
+
+		let err = parse_hex(line("ü0")).unwrap_err();
+		assert_eq!((err.column, err.text.as_str()), (1, "ü"));
+	}
+
+	#[test]
+	fn test_parse_base64() {
+		assert_eq!(parse_base64(line("aGVsbG8=")), Ok(b"hello".to_vec()));
+		assert_eq!(parse_base64(line("aGVs bG8")), Ok(b"hello".to_vec()));
+		assert_eq!(parse_base64(line("4pWU")), Ok(vec![0xe2, 0x95, 0x94]));
+
+		let err = parse_base64(line("aGV*bG8=")).unwrap_err();
+		assert_eq!(
+			(err.column, err.message.as_str()),
+			(4, "Expected a base64 digit")
+		);
+		let err = parse_base64(line("aGVsbG=8")).unwrap_err();
+		assert_eq!(
+			(err.column, err.message.as_str()),
+			(8, "Unexpected data after padding")
+		);
+		let err = parse_base64(line("aGVsbG8==")).unwrap_err();
+		assert_eq!(
+			(err.column, err.message.as_str()),
+			(9, "Unexpected padding")
+		);
+		let err = parse_base64(line("aGVsb")).unwrap_err();
+		assert_eq!(err.column, 6);
+		let err = parse_base64(line("aGVsbG=")).unwrap_err();
+		assert_eq!(err.message, "Expected padding up to a group of four digits");
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(
+			parse(b"e295\n94\n", Format::Hex),
+			Ok(vec![0xe2, 0x95, 0x94])
+		);
+		assert_eq!(
+			parse(b"aGVs\nbG8=\n", Format::Base64),
+			Ok(b"hello".to_vec())
+		);
+		assert_eq!(parse(b"\xff\x00", Format::Binary), Ok(vec![0xff, 0x00]));
+
+		let err = parse(b"e2\n9\xff", Format::Hex).unwrap_err();
+		assert_eq!((err.line, err.column), (2, 2));
+		let err = parse(b"e2\n95 9", Format::Hex).unwrap_err();
+		assert_eq!((err.line, err.column), (2, 4));
+	}
+
+	#[test]
+	fn test_guess() {
+		assert_eq!(Format::guess(b"e29594"), Format::Hex);
+		assert_eq!(Format::guess(b"aGVsbG8="), Format::Base64);
+		assert_eq!(Format::guess(b"\xe2\x95\x94"), Format::Binary);
+		assert_eq!("base64".parse(), Ok(Format::Base64));
+	}
+}
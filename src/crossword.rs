@@ -1,36 +1,168 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub mod grid;
+
+use std::fmt::Display;
+
+use itertools::Itertools as _;
+
+use crate::{InputError, error::Line};
+
+pub const UNKNOWN: char = '.';
+
+/* One slot of a crossword, like `.a..e.`: a word of that length with the
+ * letters that are already known. */
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrosswordEntry {
-	word_len: usize,
-	ch_index: usize,
-	ch: char,
+	pattern: Vec<Option<char>>,
 }
 
 impl CrosswordEntry {
+	pub fn new(pattern: Vec<Option<char>>) -> Self {
+		Self { pattern }
+	}
+
+	pub fn len(&self) -> usize {
+		self.pattern.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pattern.is_empty()
+	}
+
+	pub fn pattern(&self) -> &[Option<char>] {
+		&self.pattern
+	}
+
+	pub fn known(&self) -> impl Iterator<Item = (usize, char)> {
+		self.pattern
+			.iter()
+			.enumerate()
+			.filter_map(|(i, ch)| ch.map(|ch| (i, ch)))
+	}
+
 	pub fn matches(&self, word: &str) -> bool {
-		word.chars().count() == self.word_len
-			&& word
-				.chars()
-				.nth(self.ch_index)
-				.is_some_and(|ch| ch == self.ch)
+		let mut chars = word.chars();
+
+		self.pattern.iter().all(|&known| {
+			chars
+				.next()
+				.is_some_and(|ch| known.is_none_or(|known| ch == known))
+		}) && chars.next().is_none()
+	}
+
+	/* The index of the only word in `words` this entry matches. If there isn't
+	 * exactly one, how many it does match. */
+	pub fn find_word<S: AsRef<str>>(&self, words: &[S]) -> Result<usize, usize> {
+		words
+			.iter()
+			.positions(|word| self.matches(word.as_ref()))
+			.exactly_one()
+			.map_err(Iterator::count)
 	}
 }
 
-pub fn parse_crossword(
-	lines: impl Iterator<Item = impl AsRef<str>>,
-) -> impl Iterator<Item = CrosswordEntry> {
-	lines.map(|line| {
-		let line = line.as_ref().trim();
-		let word_len = line.chars().count();
-		let (ch_index, ch) = line
-			.trim()
-			.char_indices()
-			.find(|(_, ch)| *ch != '.')
-			.unwrap();
-
-		CrosswordEntry {
-			word_len,
-			ch_index,
-			ch,
+impl Display for CrosswordEntry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for ch in &self.pattern {
+			write!(f, "{}", ch.unwrap_or(UNKNOWN))?;
 		}
-	})
+
+		Ok(())
+	}
+}
+
+/* Parses one pattern per line. Lines may be indented, but a pattern can't
+ * contain whitespace. */
+pub fn parse_crossword<'a>(
+	lines: impl IntoIterator<Item = Line<'a>>,
+) -> Result<Vec<CrosswordEntry>, InputError> {
+	lines
+		.into_iter()
+		.map(|line| {
+			let pattern = line.text.trim();
+			if pattern.is_empty() {
+				return Err(line.end_error("a crossword pattern"));
+			}
+
+			if let Some((offset, ch)) = pattern.char_indices().find(|(_, ch)| ch.is_whitespace()) {
+				let text = &pattern[offset..offset + ch.len_utf8()];
+				return Err(line.error(text, "Unexpected whitespace in crossword pattern"));
+			}
+
+			Ok(CrosswordEntry::new(
+				pattern
+					.chars()
+					.map(|ch| (ch != UNKNOWN).then_some(ch))
+					.collect(),
+			))
+		})
+		.collect()
+}
+
+/* Checks that every entry matches exactly one of `words`, and reports the
+ * first that doesn't on its line */
+pub fn check_words<S: AsRef<str>>(
+	lines: &[Line],
+	entries: &[CrosswordEntry],
+	words: &[S],
+) -> Result<(), InputError> {
+	for (line, entry) in lines.iter().zip(entries) {
+		if let Err(found) = entry.find_word(words) {
+			return Err(line.error(
+				line.text.trim(),
+				format!("Expected one word to fit {entry}, found {found}"),
+			));
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::numbered_lines;
+
+	#[test]
+	fn test_parse_crossword() {
+		let entries = parse_crossword(numbered_lines("  .a..e.\n  ....\nüb.")).unwrap();
+
+		assert_eq!(entries[0].to_string(), ".a..e.");
+		assert_eq!(entries[0].known().collect::<Vec<_>>(), [(1, 'a'), (4, 'e')]);
+		assert_eq!(entries[1].known().count(), 0);
+		assert_eq!(entries[2].known().collect::<Vec<_>>(), [(0, 'ü'), (1, 'b')]);
+
+		assert!(entries[0].matches("cakeen"));
+		assert!(!entries[0].matches("cakes"));
+		assert!(!entries[0].matches("cakesss"));
+		assert!(entries[1].matches("ørkn"));
+		assert!(entries[2].matches("übe"));
+	}
+
+	#[test]
+	fn test_check_words() {
+		let lines = numbered_lines("..t.\n  b..").collect::<Vec<_>>();
+		let entries = parse_crossword(lines.iter().copied()).unwrap();
+
+		assert_eq!(entries[0].find_word(&["data", "bob"]), Ok(0));
+		assert!(check_words(&lines, &entries, &["data", "bob"]).is_ok());
+
+		let err = check_words(&lines, &entries, &["data"]).unwrap_err();
+		assert_eq!((err.line, err.column), (2, 3));
+		assert_eq!(err.message, "Expected one word to fit b.., found 0");
+
+		let err = check_words(&lines, &entries, &["bob", "data", "bee"]).unwrap_err();
+		assert_eq!(err.message, "Expected one word to fit b.., found 2");
+	}
+
+	#[test]
+	fn test_parse_crossword_error() {
+		let err = parse_crossword(numbered_lines("..a.\n   \n")).unwrap_err();
+		assert_eq!(
+			(err.line, err.message.as_str()),
+			(2, "Expected a crossword pattern")
+		);
+
+		let err = parse_crossword(numbered_lines(" .a b.")).unwrap_err();
+		assert_eq!((err.line, err.column), (1, 4));
+	}
 }
@@ -0,0 +1,350 @@
+use std::fmt::Display;
+
+use super::{CrosswordEntry, UNKNOWN};
+use crate::{InputError, error::Line};
+
+pub const BLOCK: char = '#';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+	Across,
+	Down,
+}
+
+/* A run of at least two open cells, starting at `row`, `column` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+	pub direction: Direction,
+	pub row: usize,
+	pub column: usize,
+	pub len: usize,
+}
+
+impl Slot {
+	pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+		let Slot {
+			direction,
+			row,
+			column,
+			..
+		} = *self;
+
+		(0..self.len).map(move |i| match direction {
+			Direction::Across => (row, column + i),
+			Direction::Down => (row + i, column),
+		})
+	}
+}
+
+/* Slot `across` and slot `down` share a cell: letter `across_index` of one is
+ * letter `down_index` of the other. Slots are indices into `Grid::slots`. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crossing {
+	pub across: usize,
+	pub across_index: usize,
+	pub down: usize,
+	pub down_index: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+	/* The index of the word for every slot */
+	pub words: Vec<usize>,
+	pub unique: bool,
+}
+
+/* Open cells are `.` or a known letter, blocks are `#` or spaces. Rows may
+ * have different lengths, missing cells are blocks. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+	cells: Vec<Vec<Option<Option<char>>>>,
+	slots: Vec<Slot>,
+	crossings: Vec<Crossing>,
+}
+
+impl Grid {
+	pub fn parse<'a>(lines: impl IntoIterator<Item = Line<'a>>) -> Result<Self, InputError> {
+		let cells = lines
+			.into_iter()
+			.map(|line| {
+				line.text
+					.char_indices()
+					.map(|(offset, ch)| match ch {
+						BLOCK | ' ' => Ok(None),
+						UNKNOWN => Ok(Some(None)),
+						ch if ch.is_alphabetic() => Ok(Some(Some(ch))),
+						ch => Err(line.error_at_offset(
+							offset,
+							&line.text[offset..offset + ch.len_utf8()],
+							format!("Expected a letter, '{UNKNOWN}' or '{BLOCK}'"),
+						)),
+					})
+					.collect::<Result<Vec<_>, _>>()
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(Self::new(cells))
+	}
+
+	fn new(cells: Vec<Vec<Option<Option<char>>>>) -> Self {
+		let mut grid = Self {
+			cells,
+			slots: Vec::new(),
+			crossings: Vec::new(),
+		};
+
+		let height = grid.cells.len();
+		let width = grid.cells.iter().map(Vec::len).max().unwrap_or(0);
+
+		for direction in [Direction::Across, Direction::Down] {
+			let (outer, inner) = match direction {
+				Direction::Across => (height, width),
+				Direction::Down => (width, height),
+			};
+
+			for o in 0..outer {
+				let mut start = 0;
+				for i in 0..=inner {
+					let (row, column) = match direction {
+						Direction::Across => (o, i),
+						Direction::Down => (i, o),
+					};
+
+					if grid.is_open(row, column) {
+						continue;
+					}
+
+					if i - start >= 2 {
+						let (row, column) = match direction {
+							Direction::Across => (o, start),
+							Direction::Down => (start, o),
+						};
+						grid.slots.push(Slot {
+							direction,
+							row,
+							column,
+							len: i - start,
+						});
+					}
+					start = i + 1;
+				}
+			}
+		}
+
+		for (across, across_slot) in grid.slots.iter().enumerate() {
+			for (down, down_slot) in grid.slots.iter().enumerate() {
+				if across_slot.direction != Direction::Across
+					|| down_slot.direction != Direction::Down
+				{
+					continue;
+				}
+
+				let across_index = down_slot.column.wrapping_sub(across_slot.column);
+				let down_index = across_slot.row.wrapping_sub(down_slot.row);
+				if across_index < across_slot.len && down_index < down_slot.len {
+					grid.crossings.push(Crossing {
+						across,
+						across_index,
+						down,
+						down_index,
+					});
+				}
+			}
+		}
+
+		grid
+	}
+
+	fn cell(&self, row: usize, column: usize) -> Option<Option<char>> {
+		self.cells.get(row)?.get(column).copied().flatten()
+	}
+
+	pub fn is_open(&self, row: usize, column: usize) -> bool {
+		self.cell(row, column).is_some()
+	}
+
+	pub fn letter(&self, row: usize, column: usize) -> Option<char> {
+		self.cell(row, column).flatten()
+	}
+
+	pub fn slots(&self) -> &[Slot] {
+		&self.slots
+	}
+
+	pub fn crossings(&self) -> &[Crossing] {
+		&self.crossings
+	}
+
+	pub fn entry(&self, slot: &Slot) -> CrosswordEntry {
+		CrosswordEntry::new(
+			slot.cells()
+				.map(|(row, column)| self.letter(row, column))
+				.collect(),
+		)
+	}
+
+	/* Assigns every slot a different word that fits its pattern and agrees
+	 * with every crossing slot. Stops looking after a second solution, which
+	 * is enough to tell whether the first one is unique. */
+	pub fn solve(&self, words: &[impl AsRef<str>]) -> Option<Solution> {
+		let mut candidates = self
+			.slots
+			.iter()
+			.enumerate()
+			.map(|(i, slot)| {
+				let entry = self.entry(slot);
+				let candidates = (0..words.len())
+					.filter(|&w| entry.matches(words[w].as_ref()))
+					.collect::<Vec<_>>();
+				(i, candidates)
+			})
+			.collect::<Vec<_>>();
+
+		let words = words
+			.iter()
+			.map(|word| word.as_ref().chars().collect::<Vec<_>>())
+			.collect::<Vec<_>>();
+
+		/* Slots with the fewest candidates first prunes the search the most */
+		candidates.sort_by_key(|(_, candidates)| candidates.len());
+
+		let mut search = Search {
+			grid: self,
+			words: &words,
+			candidates: &candidates,
+			assigned: vec![None; self.slots.len()],
+			first: None,
+			count: 0,
+		};
+		search.run(0);
+
+		search.first.map(|words| Solution {
+			words,
+			unique: search.count == 1,
+		})
+	}
+}
+
+struct Search<'a> {
+	grid: &'a Grid,
+	words: &'a [Vec<char>],
+	candidates: &'a [(usize, Vec<usize>)],
+	assigned: Vec<Option<usize>>,
+	first: Option<Vec<usize>>,
+	count: usize,
+}
+
+impl Search<'_> {
+	fn run(&mut self, depth: usize) {
+		if self.count >= 2 {
+			return;
+		}
+
+		let Some((slot, candidates)) = self.candidates.get(depth) else {
+			self.count += 1;
+			if self.first.is_none() {
+				self.first = Some(self.assigned.iter().map(|word| word.unwrap()).collect());
+			}
+			return;
+		};
+
+		for &word in candidates {
+			if self.assigned.contains(&Some(word)) || !self.fits(*slot, word) {
+				continue;
+			}
+
+			self.assigned[*slot] = Some(word);
+			self.run(depth + 1);
+			self.assigned[*slot] = None;
+		}
+	}
+
+	fn fits(&self, slot: usize, word: usize) -> bool {
+		self.grid.crossings.iter().all(|crossing| {
+			let (index, other, other_index) = if crossing.across == slot {
+				(crossing.across_index, crossing.down, crossing.down_index)
+			} else if crossing.down == slot {
+				(crossing.down_index, crossing.across, crossing.across_index)
+			} else {
+				return true;
+			};
+
+			self.assigned[other]
+				.is_none_or(|other| self.words[other][other_index] == self.words[word][index])
+		})
+	}
+}
+
+impl Display for Grid {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for row in &self.cells {
+			for cell in row {
+				let ch = match cell {
+					None => BLOCK,
+					Some(letter) => letter.unwrap_or(UNKNOWN),
+				};
+				write!(f, "{ch}")?;
+			}
+			writeln!(f)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::numbered_lines;
+
+	const WORDS: [&str; 5] = ["cat", "cow", "tin", "wan", "dog"];
+
+	#[test]
+	fn test_slots() {
+		let grid = Grid::parse(numbered_lines("c..\n.#.\n...")).unwrap();
+
+		assert_eq!(
+			grid.slots()
+				.iter()
+				.map(|slot| grid.entry(slot).to_string())
+				.collect::<Vec<_>>(),
+			["c..", "...", "c..", "..."]
+		);
+		assert_eq!(grid.crossings().len(), 4);
+		assert_eq!(
+			grid.crossings()[1],
+			Crossing {
+				across: 0,
+				across_index: 2,
+				down: 3,
+				down_index: 0
+			}
+		);
+	}
+
+	#[test]
+	fn test_solve() {
+		/* Transposing the solution gives a second one */
+		let grid = Grid::parse(numbered_lines("c..\n.#.\n...")).unwrap();
+		let solution = grid.solve(&WORDS).unwrap();
+		assert!(!solution.unique);
+
+		let grid = Grid::parse(numbered_lines("ca.\n.#.\n...")).unwrap();
+		assert_eq!(
+			grid.solve(&WORDS),
+			Some(Solution {
+				words: vec![0, 3, 1, 2],
+				unique: true
+			})
+		);
+
+		let grid = Grid::parse(numbered_lines("x..\n.#.\n...")).unwrap();
+		assert_eq!(grid.solve(&WORDS), None);
+	}
+
+	#[test]
+	fn test_parse_error() {
+		let err = Grid::parse(numbered_lines("c..\n.?.")).unwrap_err();
+		assert_eq!((err.line, err.column), (2, 2));
+	}
+}
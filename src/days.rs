@@ -0,0 +1,118 @@
+use std::{
+	hint::black_box,
+	time::{Duration, Instant},
+};
+
+use crate::{InputError, Puzzle};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day17;
+pub mod day18;
+
+pub use day01::Day01;
+pub use day02::Day02;
+pub use day03::Day03;
+pub use day04::Day04;
+pub use day05::Day05;
+pub use day06::Day06;
+pub use day07::Day07;
+pub use day08::Day08;
+pub use day09::Day09;
+pub use day10::Day10;
+pub use day11::Day11;
+pub use day12::Day12;
+pub use day13::Day13;
+pub use day14::Day14;
+pub use day15::Day15;
+pub use day17::Day17;
+pub use day18::Day18;
+
+pub type Solver = fn(&str) -> Result<String, InputError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+	pub number: u8,
+	pub solve: Solver,
+	pub measure: fn(&str) -> Result<Measurement, InputError>,
+	pub scale: fn(&str, usize) -> Option<String>,
+}
+
+impl Day {
+	const fn new<P: Puzzle>(number: u8) -> Self {
+		Self {
+			number,
+			solve: answer::<P>,
+			measure: measure::<P>,
+			scale: P::scale,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+	pub answer: String,
+	pub parse: Duration,
+	pub solve: Duration,
+}
+
+pub const DAYS: [Day; 17] = [
+	Day::new::<Day01>(1),
+	Day::new::<Day02>(2),
+	Day::new::<Day03>(3),
+	Day::new::<Day04>(4),
+	Day::new::<Day05>(5),
+	Day::new::<Day06>(6),
+	Day::new::<Day07>(7),
+	Day::new::<Day08>(8),
+	Day::new::<Day09>(9),
+	Day::new::<Day10>(10),
+	Day::new::<Day11>(11),
+	Day::new::<Day12>(12),
+	Day::new::<Day13>(13),
+	Day::new::<Day14>(14),
+	Day::new::<Day15>(15),
+	Day::new::<Day17>(17),
+	Day::new::<Day18>(18),
+];
+
+pub fn get(day: u8) -> Option<Day> {
+	DAYS.into_iter().find(|d| d.number == day)
+}
+
+pub fn solver(day: u8) -> Option<Solver> {
+	get(day).map(|day| day.solve)
+}
+
+fn answer<P: Puzzle>(input: &str) -> Result<String, InputError> {
+	P::run(input).map(|answer| answer.to_string())
+}
+
+fn measure<P: Puzzle>(input: &str) -> Result<Measurement, InputError> {
+	let start = Instant::now();
+	let parsed = P::parse(black_box(input))?;
+	let parse = start.elapsed();
+
+	let start = Instant::now();
+	let answer = P::solve(black_box(&parsed));
+	let solve = start.elapsed();
+
+	Ok(Measurement {
+		answer: answer.to_string(),
+		parse,
+		solve,
+	})
+}
@@ -0,0 +1,75 @@
+use crate::{InputError, Puzzle, puzzle::repeat_lines, text::Unit};
+
+pub struct Day01;
+
+impl Puzzle for Day01 {
+	type Input = Vec<String>;
+	type Answer = u32;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		Ok(input.lines().map(String::from).collect())
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		input.iter().map(|message| cost(message)).sum()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_lines(input, factor))
+	}
+}
+
+const SMS_THRESHOLD: usize = 160;
+const TWEET_THRESHOLD: usize = 140;
+
+pub fn cost(input: &str) -> u32 {
+	cost_in(input, Unit::CodePoints)
+}
+
+/* Like `cost`, with tweets measured in `unit` instead of code points */
+pub fn cost_in(input: &str, unit: Unit) -> u32 {
+	cost_with(input, |message| unit.len(message) <= TWEET_THRESHOLD)
+}
+
+/* Like `cost`, with `fits_tweet` deciding what can be sent as a tweet, like
+ * `tweet::fits` for the real rules */
+pub fn cost_with(input: &str, fits_tweet: impl Fn(&str) -> bool) -> u32 {
+	match (input.len() <= SMS_THRESHOLD, fits_tweet(input)) {
+		(true, true) => 13,
+		(true, false) => 11,
+		(false, true) => 7,
+		(false, false) => 0,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::text::tweet;
+
+	#[test]
+	fn test_cost() {
+		assert_eq!(cost("Short message."), 13);
+		assert_eq!(cost(&"ü".repeat(100)), 7);
+		assert_eq!(cost(&"x".repeat(150)), 11);
+		assert_eq!(cost(&"ü".repeat(150)), 0);
+	}
+
+	#[test]
+	fn test_cost_in() {
+		let flags = "🇳🇱".repeat(40);
+		assert_eq!(cost_in(&flags, Unit::CodePoints), 7);
+		assert_eq!(cost_in(&flags, Unit::Graphemes), 7);
+		assert_eq!(cost_in(&"e\u{301}".repeat(75), Unit::CodePoints), 0);
+		assert_eq!(cost_in(&"e\u{301}".repeat(75), Unit::Graphemes), 7);
+		assert_eq!(cost_in(&"💩".repeat(71), Unit::CodePoints), 7);
+		assert_eq!(cost_in(&"💩".repeat(71), Unit::Utf16), 0);
+	}
+
+	#[test]
+	fn test_cost_with() {
+		assert_eq!(cost_with(&"x".repeat(150), tweet::fits), 13);
+		assert_eq!(cost_with(&"日".repeat(141), tweet::fits), 0);
+		assert_eq!(cost_with(&"日".repeat(140), tweet::fits), 7);
+	}
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset, TimeDelta, Utc};
+use itertools::Itertools;
+
+use crate::{InputError, Puzzle, error::numbered_lines, puzzle::repeat_lines};
+
+pub struct Day02;
+
+impl Puzzle for Day02 {
+	type Input = Vec<DateTime<Utc>>;
+	type Answer = Rfc3339;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let instants = numbered_lines(input)
+			.map(|line| line.parse(line.text))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let repeated = instants
+			.iter()
+			.counts()
+			.into_values()
+			.filter(|&count| count >= 4)
+			.count();
+		if repeated != 1 {
+			return Err(InputError::at_end(
+				input,
+				format!("Expected one instant that occurs four or more times, found {repeated}"),
+			));
+		}
+
+		Ok(instants)
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let instant = input
+			.iter()
+			.counts()
+			.into_iter()
+			.filter_map(|(instant, count)| (count >= 4).then_some(instant))
+			.exactly_one()
+			.expect("Checked when parsing");
+
+		Rfc3339(instant.fixed_offset())
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		/* Repeating lines as-is would create more instants that occur four
+		 * times, so every copy is shifted by a different number of seconds. */
+		let lines = input.lines().collect::<Vec<_>>();
+		let mut scaled = repeat_lines(input, 1);
+
+		for copy in 1..factor {
+			for (i, line) in lines.iter().enumerate() {
+				let datetime = DateTime::parse_from_rfc3339(line).ok()?;
+				let shift = TimeDelta::seconds((copy * lines.len() + i) as i64);
+				scaled.push_str(&(datetime + shift).to_rfc3339());
+				scaled.push('\n');
+			}
+		}
+
+		Some(scaled)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rfc3339(pub DateTime<FixedOffset>);
+
+impl Display for Rfc3339 {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0.to_rfc3339())
+	}
+}
@@ -0,0 +1,28 @@
+use crate::{InputError, Puzzle, puzzle::repeat_lines};
+
+pub struct Day03;
+
+impl Puzzle for Day03 {
+	type Input = Vec<String>;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		Ok(input.lines().map(String::from).collect())
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		input.iter().filter(|password| is_valid(password)).count()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_lines(input, factor))
+	}
+}
+
+pub fn is_valid(password: &str) -> bool {
+	(4..=12).contains(&password.chars().count())
+		&& password.chars().any(|ch| ch.is_ascii_digit())
+		&& password.chars().any(|ch| ch.is_lowercase())
+		&& password.chars().any(|ch| ch.is_uppercase())
+		&& !password.is_ascii()
+}
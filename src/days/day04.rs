@@ -0,0 +1,100 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta};
+use chrono_tz::Tz;
+use itertools::Itertools;
+
+use crate::{
+	InputError, Puzzle,
+	error::{Line, NomError, numbered_lines},
+};
+
+pub struct Day04;
+
+impl Puzzle for Day04 {
+	type Input = Vec<Journey>;
+	type Answer = i64;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		numbered_lines(input)
+			.chunks(3)
+			.into_iter()
+			.map(|mut chunk| {
+				let departure = chunk.next().unwrap();
+				let Some(arrival) = chunk.next() else {
+					return Err(
+						departure.error(departure.text, "Expected an arrival on the next line")
+					);
+				};
+
+				Ok(Journey {
+					departure: parse_entry(departure)?,
+					arrival: parse_entry(arrival)?,
+				})
+			})
+			.collect()
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		input
+			.iter()
+			.map(|journey| journey.duration().num_minutes())
+			.sum()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(vec![input.trim_end(); factor].join("\n\n"))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Journey {
+	pub departure: DateTime<Tz>,
+	pub arrival: DateTime<Tz>,
+}
+
+impl Journey {
+	pub fn duration(&self) -> TimeDelta {
+		self.arrival - self.departure
+	}
+}
+
+pub fn parse_entry(entry: Line) -> Result<DateTime<Tz>, InputError> {
+	use nom::{
+		Parser as _,
+		bytes::complete::take_while1,
+		character::complete::space1,
+		combinator::{map_res, rest},
+		error::context,
+	};
+
+	type IResult<'a, O> = nom::IResult<&'a str, O, NomError<'a>>;
+
+	fn word(s: &str) -> IResult<'_, &str> {
+		take_while1(|c: char| !c.is_ascii_whitespace())(s)
+	}
+
+	fn tz(s: &str) -> IResult<'_, Tz> {
+		context("a timezone", map_res(word, |s| s.parse())).parse(s)
+	}
+
+	fn datetime(s: &str) -> IResult<'_, NaiveDateTime> {
+		context(
+			"a date like 'Mar 04, 2020, 10:00'",
+			map_res(rest, |s| {
+				NaiveDateTime::parse_from_str(s, "%b %d, %Y, %H:%M")
+			}),
+		)
+		.parse(s)
+	}
+
+	let (_, _, tz, _, datetime) = (word, space1, tz, space1, datetime)
+		.parse(entry.text)
+		.map_err(|err| entry.nom_error(err))?
+		.1;
+
+	datetime.and_local_timezone(tz).single().ok_or_else(|| {
+		entry.error(
+			entry.text,
+			format!("Ambiguous or non-existent local time in {tz}"),
+		)
+	})
+}
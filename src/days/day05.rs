@@ -0,0 +1,98 @@
+use std::ops::Range;
+
+use crate::{InputError, Puzzle, error::numbered_lines, puzzle::repeat_lines, text::Unit};
+
+const PILE_OF_POO: &str = "💩";
+
+pub struct Day05;
+
+impl Puzzle for Day05 {
+	type Input = Vec<Row>;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		parse_rows(input, Unit::CodePoints)
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let Some(width) = input.first().map(Row::width) else {
+			return 0;
+		};
+
+		input
+			.iter()
+			.enumerate()
+			.map(|(index, row)| row.cell(index * 2 % width))
+			.filter(|&cell| cell == PILE_OF_POO)
+			.count()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_lines(input, factor))
+	}
+}
+
+/* A row of the map, with its cells in some unit */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+	text: String,
+	cells: Vec<Range<usize>>,
+}
+
+impl Row {
+	pub fn new(text: &str, unit: Unit) -> Self {
+		Self {
+			text: text.to_string(),
+			cells: unit.spans(text),
+		}
+	}
+
+	pub fn width(&self) -> usize {
+		self.cells.len()
+	}
+
+	pub fn cell(&self, index: usize) -> &str {
+		&self.text[self.cells[index].clone()]
+	}
+}
+
+/* Every row has to be as wide as the first, counting in `unit` */
+pub fn parse_rows(input: &str, unit: Unit) -> Result<Vec<Row>, InputError> {
+	let rows = numbered_lines(input)
+		.map(|line| (line, Row::new(line.text, unit)))
+		.collect::<Vec<_>>();
+
+	let width = rows.first().map(|(_, row)| row.width()).unwrap_or(0);
+
+	if let Some((line, _)) = rows
+		.iter()
+		.find(|(_, row)| row.width() == 0 || row.width() != width)
+	{
+		return Err(line.error(line.text, format!("Expected a line of {width} {unit}")));
+	}
+
+	Ok(rows.into_iter().map(|(_, row)| row).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_rows() {
+		let input = "ae\u{301}💩\n🇳🇱🇧🇪c\n";
+
+		let rows = parse_rows(input, Unit::Graphemes).unwrap();
+		assert_eq!(rows[0].cell(1), "e\u{301}");
+		assert_eq!(rows[1].cell(1), "🇧🇪");
+		assert_eq!(Day05::solve(&rows), 0);
+
+		let err = parse_rows(input, Unit::CodePoints).unwrap_err();
+		assert_eq!(err.line, 2);
+		assert_eq!(err.message, "Expected a line of 4 code-points");
+
+		let rows = parse_rows("💩a\nb💩\n", Unit::Utf16).unwrap();
+		assert_eq!(rows[1].cell(1), "💩");
+		assert_eq!(Day05::solve(&rows), 2);
+	}
+}
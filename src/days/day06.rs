@@ -0,0 +1,103 @@
+use crate::{
+	InputError, Puzzle,
+	crossword::{CrosswordEntry, check_words, parse_crossword},
+	encoding::mojibake::{self, MojibakeError},
+	error::numbered_lines,
+	puzzle::repeat_last_section,
+};
+
+pub struct Day06;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+	pub words: Vec<String>,
+	pub crossword: Vec<CrosswordEntry>,
+}
+
+impl Puzzle for Day06 {
+	type Input = Input;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let mut lines = numbered_lines(input);
+
+		let words = (&mut lines)
+			.take_while(|line| !line.text.is_empty())
+			.map(|line| line.text.to_string())
+			.collect::<Vec<_>>();
+		let lines = lines.collect::<Vec<_>>();
+		let crossword = parse_crossword(lines.iter().copied())?;
+		check_words(&lines, &crossword, &repair_words(&words))?;
+
+		Ok(Input { words, crossword })
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let words = repair_words(&input.words);
+
+		input
+			.crossword
+			.iter()
+			.map(|entry| entry.find_word(&words).expect("Checked when parsing") + 1)
+			.sum()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_last_section(input, factor))
+	}
+}
+
+fn repair_words(words: &[String]) -> Vec<String> {
+	words
+		.iter()
+		.map(|word| mojibake::repair(word).text)
+		.collect()
+}
+
+pub fn decode_miscode(str: &str) -> Result<String, MojibakeError> {
+	mojibake::unmangle(str)
+}
+
+pub fn decode_double_miscode(str: &str) -> Result<String, MojibakeError> {
+	decode_miscode(&decode_miscode(str)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::encoding::EncodeError;
+
+	#[test]
+	fn test_decode_miscode() {
+		assert_eq!(decode_miscode("religiÃ«n").as_deref(), Ok("religiën"));
+		assert_eq!(decode_miscode("kÃ¼rst").as_deref(), Ok("kürst"));
+		assert_eq!(decode_miscode("roekoeÃ«n").as_deref(), Ok("roekoeën"));
+	}
+
+	#[test]
+	fn test_decode_miscode_windows_1252() {
+		assert_eq!(decode_miscode("â€™s a â€¦").as_deref(), Ok("’s a …"));
+		assert!(matches!(
+			decode_miscode("a ∑ b"),
+			Err(MojibakeError::Unmappable(EncodeError { ch: '∑', .. }))
+		));
+	}
+
+	#[test]
+	fn test_parse_error() {
+		let err = Day06::parse("kÃ¼rst\nkurst\n\n  k...t\n").unwrap_err();
+		assert_eq!((err.line, err.column), (4, 3));
+		assert_eq!(err.message, "Expected one word to fit k...t, found 2");
+
+		let err = Day06::parse("kÃ¼rst\n\n..\n").unwrap_err();
+		assert_eq!(err.message, "Expected one word to fit .., found 0");
+	}
+
+	#[test]
+	fn test_decode_double_miscode() {
+		assert_eq!(
+			decode_double_miscode("pugilarÃÂ£o").as_deref(),
+			Ok("pugilarão")
+		);
+	}
+}
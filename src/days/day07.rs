@@ -0,0 +1,72 @@
+use chrono::{DateTime, FixedOffset, Offset as _, TimeDelta, Timelike as _};
+use chrono_tz::Tz;
+
+use crate::{InputError, Puzzle, error::numbered_lines, puzzle::repeat_lines};
+
+pub struct Day07;
+
+impl Puzzle for Day07 {
+	type Input = Vec<Entry>;
+	type Answer = u64;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		numbered_lines(input)
+			.map(|line| {
+				let mut columns = line.text.split_whitespace();
+				let mut column = |what| columns.next().ok_or_else(|| line.end_error(what));
+
+				let datetime = line.parse(column("a timestamp")?)?;
+
+				let correct_duration: i64 = line.parse(column("the correct duration")?)?;
+				let correct_duration = TimeDelta::minutes(correct_duration);
+
+				let wrong_duration: i64 = line.parse(column("the wrong duration")?)?;
+				let wrong_duration = TimeDelta::minutes(wrong_duration);
+
+				Ok(Entry {
+					datetime,
+					correct_duration,
+					wrong_duration,
+				})
+			})
+			.collect()
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		input
+			.iter()
+			.map(Entry::corrected)
+			.enumerate()
+			.map(|(i, datetime)| (i + 1) as u64 * datetime.hour() as u64)
+			.sum()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_lines(input, factor))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+	pub datetime: DateTime<FixedOffset>,
+	pub correct_duration: TimeDelta,
+	pub wrong_duration: TimeDelta,
+}
+
+impl Entry {
+	pub fn corrected(&self) -> DateTime<Tz> {
+		deduce_timezone(&self.datetime) - self.wrong_duration + self.correct_duration
+	}
+}
+
+pub fn deduce_timezone(datetime: &DateTime<FixedOffset>) -> DateTime<Tz> {
+	if is_possible_timezone(datetime, chrono_tz::America::Halifax) {
+		datetime.with_timezone(&chrono_tz::America::Halifax)
+	} else {
+		datetime.with_timezone(&chrono_tz::America::Santiago)
+	}
+}
+
+fn is_possible_timezone(datetime: &DateTime<FixedOffset>, tz: Tz) -> bool {
+	&datetime.with_timezone(&tz).offset().fix() == datetime.offset()
+}
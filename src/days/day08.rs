@@ -0,0 +1,75 @@
+use itertools::Itertools as _;
+
+use crate::{InputError, Puzzle, puzzle::repeat_lines, text::fold::fold_letters};
+
+pub struct Day08;
+
+impl Puzzle for Day08 {
+	type Input = Vec<String>;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		Ok(input.lines().map(String::from).collect())
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		input.iter().filter(|password| is_valid(password)).count()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_lines(input, factor))
+	}
+}
+
+pub fn is_valid(password: &str) -> bool {
+	/* Counted as typed, as folding composes accents onto their letters */
+	let length = password.chars().count();
+	let password = deaccent(password);
+
+	(4..=12).contains(&length)
+		&& password.chars().any(|ch| ch.is_ascii_digit())
+		&& password.chars().any(is_vowel)
+		&& password.chars().any(is_consonant)
+		&& !has_recurring_letters(&password)
+}
+
+pub fn deaccent(str: &str) -> String {
+	fold_letters(str)
+}
+
+fn is_vowel(ch: char) -> bool {
+	matches!(ch.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn is_consonant(ch: char) -> bool {
+	ch.is_alphabetic() && !is_vowel(ch)
+}
+
+fn has_recurring_letters(password: &str) -> bool {
+	password
+		.chars()
+		.filter(|ch| ch.is_ascii_alphabetic())
+		.map(|ch| ch.to_ascii_lowercase())
+		.counts()
+		.into_iter()
+		.any(|(_, counts)| counts > 1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_is_valid() {
+		assert!(!is_valid("iS0"), "too short");
+		assert!(is_valid("Ædö3"));
+		assert!(!is_valid("Ødo3"), "ø is an o");
+		assert!(!is_valid("łołł9a"), "ł is an l");
+		assert!(!is_valid("abcdefghijk9Ø"), "too long");
+		/* Folded a letter at a time, so unlike `fold`, ß is one s and æ one a */
+		assert!(is_valid("Maße9"), "ß is one s");
+		assert!(!is_valid("Maßes9"), "ß is an s");
+		assert!(is_valid("Ærø12"), "æ is one a");
+		assert!(!is_valid("Æra12"), "æ is an a");
+	}
+}
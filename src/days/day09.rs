@@ -0,0 +1,211 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use chrono::NaiveDate;
+use itertools::Itertools;
+
+use crate::{
+	InputError, Puzzle,
+	error::{Line, NomError, numbered_lines},
+	puzzle::repeat_lines,
+};
+
+pub type DateComponents = [u8; 3];
+
+const _9_11: NaiveDate = NaiveDate::from_ymd_opt(2001, 9, 11).unwrap();
+
+pub struct Day09;
+
+impl Puzzle for Day09 {
+	type Input = BTreeMap<String, Vec<DateComponents>>;
+	type Answer = Names;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let dates = parse_input(numbered_lines(input))?;
+
+		for (name, dates) in &dates {
+			let found = DateFormat::possible(dates).count();
+			if found != 1 {
+				/* Reported where the name is first mentioned */
+				let (line, at) = numbered_lines(input)
+					.find_map(|line| {
+						let (_, names) = line.text.split_once(": ")?;
+						let at = names.split(", ").find(|other| other == name)?;
+						Some((line, at))
+					})
+					.expect("Names come from the input");
+
+				return Err(line.error(
+					at,
+					format!("Expected the dates of {name} to fit one format, found {found}"),
+				));
+			}
+		}
+
+		Ok(dates)
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let names = input
+			.iter()
+			.filter_map(|(name, dates)| {
+				let format = DateFormat::possible(dates)
+					.next()
+					.expect("Checked when parsing");
+
+				let wrote_on_9_11 = dates
+					.iter()
+					.any(|date| format.parse(*date).unwrap() == _9_11);
+
+				wrote_on_9_11.then(|| name.clone())
+			})
+			.collect();
+
+		Names(names)
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_lines(input, factor))
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Names(pub Vec<String>);
+
+impl Display for Names {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0.iter().join(" "))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateFormat {
+	Dmy,
+	Mdy,
+	Ymd,
+	Ydm,
+}
+
+impl DateFormat {
+	pub const FORMATS: [Self; 4] = [Self::Dmy, Self::Mdy, Self::Ymd, Self::Ydm];
+
+	pub fn parse(&self, components: DateComponents) -> Option<NaiveDate> {
+		let [y, m, d] = match self {
+			DateFormat::Dmy => [components[2], components[1], components[0]],
+			DateFormat::Mdy => [components[2], components[0], components[1]],
+			DateFormat::Ymd => components,
+			DateFormat::Ydm => [components[0], components[2], components[1]],
+		};
+		let (y, m, d) = (y as i32, m as u32, d as u32);
+
+		let y = if (0..=20).contains(&y) {
+			2000 + y
+		} else {
+			1900 + y
+		};
+		NaiveDate::from_ymd_opt(y, m, d)
+	}
+
+	pub fn is_possible(&self, components: DateComponents) -> bool {
+		self.parse(components).is_some()
+	}
+
+	/* The formats that every one of `dates` is valid in */
+	pub fn possible(dates: &[DateComponents]) -> impl Iterator<Item = Self> {
+		Self::FORMATS
+			.into_iter()
+			.filter(|format| dates.iter().all(|date| format.is_possible(*date)))
+	}
+}
+
+pub fn parse_input<'a>(
+	input: impl IntoIterator<Item = Line<'a>>,
+) -> Result<BTreeMap<String, Vec<DateComponents>>, InputError> {
+	use nom::{
+		Parser,
+		bytes::complete::tag,
+		character::complete::{alpha1, digit1},
+		error::context,
+		multi::separated_list1,
+		sequence::separated_pair,
+	};
+
+	type IResult<'a, O> = nom::IResult<&'a str, O, NomError<'a>>;
+
+	fn parse_entry(entry: &str) -> IResult<'_, (DateComponents, Vec<&str>)> {
+		fn parse_date_components(input: &str) -> IResult<'_, DateComponents> {
+			fn number(input: &str) -> IResult<'_, u8> {
+				context("a number", digit1.map_res(|nr: &str| nr.parse())).parse(input)
+			}
+
+			(number, tag("-"), number, tag("-"), number)
+				.map(|(x, _, y, _, z)| [x, y, z])
+				.parse(input)
+		}
+
+		separated_pair(
+			context("a date like '16-05-18'", parse_date_components),
+			context("': '", tag(": ")),
+			context("a list of names", separated_list1(tag(", "), alpha1)),
+		)
+		.parse(entry)
+	}
+
+	let mut result = BTreeMap::<String, Vec<DateComponents>>::new();
+
+	for entry in input {
+		let (_, (date_components, names)) =
+			parse_entry(entry.text).map_err(|err| entry.nom_error(err))?;
+
+		for name in names {
+			if let Some(vec) = result.get_mut(name) {
+				vec.push(date_components);
+			} else {
+				result
+					.entry(name.to_string())
+					.or_insert(vec![date_components]);
+			};
+		}
+	}
+
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_date_format_parse() {
+		let date = NaiveDate::from_ymd_opt(2001, 9, 11);
+
+		assert_eq!(DateFormat::Dmy.parse([11, 9, 1]), date);
+		assert_eq!(DateFormat::Mdy.parse([9, 11, 1]), date);
+		assert_eq!(DateFormat::Ymd.parse([1, 9, 11]), date);
+		assert_eq!(DateFormat::Ydm.parse([1, 11, 9]), date);
+		assert_eq!(
+			DateFormat::Ymd.parse([99, 12, 31]),
+			NaiveDate::from_ymd_opt(1999, 12, 31)
+		);
+		assert!(!DateFormat::Dmy.is_possible([31, 9, 11]));
+	}
+
+	#[test]
+	fn test_parse_error() {
+		let err = Day09::parse("16-05-18: Margot, Frank\n02-17-04; Peter").unwrap_err();
+
+		assert_eq!((err.line, err.column), (2, 9));
+		assert_eq!(err.message, "Expected ': '");
+
+		let err =
+			Day09::parse("16-05-18: Margot\n01-02-03: Alice, Bob\n03-02-01: Bob").unwrap_err();
+		assert_eq!((err.line, err.column, err.text.as_str()), (2, 11, "Alice"));
+		assert_eq!(
+			err.message,
+			"Expected the dates of Alice to fit one format, found 4"
+		);
+
+		let err = Day09::parse("31-12-99: Margot\n12-31-99: Margot").unwrap_err();
+		assert_eq!((err.line, err.column), (1, 11));
+		assert!(err.message.ends_with("found 0"));
+	}
+}
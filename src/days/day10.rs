@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator as _};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{InputError, Puzzle, error::numbered_lines, puzzle::repeat_last_section};
+
+pub struct Day10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+	pub passwords: BTreeMap<String, String>,
+	pub attempts: Vec<(String, String)>,
+}
+
+impl Puzzle for Day10 {
+	type Input = Input;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let mut lines = numbered_lines(input);
+
+		let passwords = (&mut lines)
+			.take_while(|line| !line.text.is_empty())
+			.map(|line| {
+				let (name, hash) = line.split_once(" ")?;
+				line.parse::<bcrypt::HashParts>(hash)?;
+				Ok((name.to_string(), hash.to_string()))
+			})
+			.collect::<Result<_, _>>()?;
+
+		let attempts = lines
+			.map(|line| {
+				let (name, pw) = line.split_once(" ")?;
+				Ok((name.to_string(), pw.to_string()))
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Input {
+			passwords,
+			attempts,
+		})
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		input
+			.attempts
+			.iter()
+			.filter(|(name, pw)| {
+				let Some(stored) = input.passwords.get(name) else {
+					return false;
+				};
+
+				possible_representations(pw)
+					.into_par_iter()
+					.any(|repr| bcrypt::verify(repr, stored).unwrap())
+			})
+			.count()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_last_section(input, factor))
+	}
+}
+
+pub fn possible_representations(password: &str) -> Vec<String> {
+	fn possible_representations(acc: &mut Vec<String>, mut prefix: String, remaining: &[char]) {
+		match remaining {
+			[] => {
+				acc.push(prefix);
+			}
+			[ch, remaining @ ..] => {
+				let mut decomposed = ch.nfd();
+				if let (Some(fst), Some(snd)) = (decomposed.next(), decomposed.next()) {
+					let mut prefix = prefix.clone();
+					prefix.push(fst);
+					prefix.push(snd);
+					possible_representations(acc, prefix, remaining);
+				}
+
+				prefix.push(*ch);
+				possible_representations(acc, prefix, remaining);
+			}
+		}
+	}
+
+	let chars = password.nfc().collect::<Vec<_>>();
+	let mut acc = Vec::new();
+
+	possible_representations(&mut acc, String::new(), &chars[..]);
+
+	acc
+}
@@ -0,0 +1,133 @@
+use std::{fmt::Display, str::FromStr};
+
+use itertools::Itertools;
+use unidecode::{unidecode, unidecode_char};
+
+use crate::{InputError, Puzzle, error::numbered_lines, puzzle::repeat_lines};
+
+pub struct Day12;
+
+impl Puzzle for Day12 {
+	type Input = Vec<Entry>;
+	type Answer = u64;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let entries = numbered_lines(input)
+			.map(|line| {
+				let entry = line.parse::<Entry>(line.text)?;
+				if !entry.last_name.chars().any(char::is_uppercase) {
+					return Err(line.error(line.text, "Expected a capital letter in the last name"));
+				}
+				line.parse::<u64>(&line.text[line.text.len() - entry.phone_number.len()..])?;
+				Ok(entry)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		if entries.len() % 2 == 0 {
+			return Err(InputError::at_end(
+				input,
+				"Expected an odd number of entries",
+			));
+		}
+
+		Ok(entries)
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		[
+			middle(input, english_sorting_key),
+			middle(input, swedish_sorting_key),
+			middle(input, dutch_sorting_key),
+		]
+		.into_iter()
+		.map(|entry| entry.phone_number.parse::<u64>().unwrap())
+		.product()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		/* The middle entry only exists for an odd number of entries */
+		let mut scaled = repeat_lines(input, factor);
+		if input.lines().count() * factor % 2 == 0 {
+			scaled.push_str(input.lines().next()?);
+			scaled.push('\n');
+		}
+
+		Some(scaled)
+	}
+}
+
+pub fn english_sorting_key(s: &str) -> String {
+	unidecode(s)
+		.chars()
+		.flat_map(char::to_lowercase)
+		.filter(|ch| ch.is_alphabetic())
+		.collect()
+}
+
+pub fn swedish_sorting_key(s: &str) -> String {
+	let mut key = String::with_capacity(s.len());
+
+	for ch in s.chars().flat_map(char::to_lowercase) {
+		match ch {
+			'å' => key.push('{'),
+			'ä' | 'æ' => key.push('|'),
+			'ö' | 'ø' => key.push('}'),
+			ch if ch.is_alphabetic() => {
+				key.push_str(unidecode_char(ch));
+			}
+			_ => { /* skip */ }
+		}
+	}
+
+	key
+}
+
+pub fn dutch_sorting_key(s: &str) -> String {
+	let first_uppercase = s
+		.chars()
+		.position(char::is_uppercase)
+		.expect("Expect last name to have a capital letter somewhere");
+	english_sorting_key(&s[first_uppercase..])
+}
+
+pub fn middle(entries: &[Entry], key: fn(&str) -> String) -> &Entry {
+	assert!(entries.len() % 2 == 1);
+
+	let sorted = entries
+		.iter()
+		.sorted_by_key(|entry| key(&entry.last_name))
+		.collect::<Vec<_>>();
+	sorted[sorted.len() / 2]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+	pub first_name: String,
+	pub last_name: String,
+	pub phone_number: String,
+}
+
+impl FromStr for Entry {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (name, phone_number) = s.split_once(": ").ok_or("Missing ': '")?;
+		let (last_name, first_name) = name.split_once(", ").ok_or("Missing ', '")?;
+
+		Ok(Self {
+			first_name: first_name.to_string(),
+			last_name: last_name.to_string(),
+			phone_number: phone_number.to_string(),
+		})
+	}
+}
+
+impl Display for Entry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}, {}: {}",
+			self.last_name, self.first_name, self.phone_number
+		)
+	}
+}
@@ -0,0 +1,138 @@
+use itertools::Itertools as _;
+
+use crate::{
+	InputError, Puzzle,
+	bytes::parse_hex,
+	crossword::{CrosswordEntry, check_words, parse_crossword},
+	encoding::{Encoding, MultiByte, detect},
+	error::numbered_lines,
+	puzzle::repeat_last_section,
+};
+
+pub struct Day13;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+	pub words: Vec<Vec<u8>>,
+	pub crossword: Vec<CrosswordEntry>,
+}
+
+impl Puzzle for Day13 {
+	type Input = Input;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let mut lines = numbered_lines(input);
+
+		let words = (&mut lines)
+			.take_while(|line| !line.text.is_empty())
+			.map(parse_hex)
+			.collect::<Result<Vec<_>, _>>()?;
+		let lines = lines.collect::<Vec<_>>();
+		let crossword = parse_crossword(lines.iter().copied())?;
+		check_words(&lines, &crossword, &decode_words(&words))?;
+
+		Ok(Input { words, crossword })
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let words = decode_words(&input.words);
+
+		input
+			.crossword
+			.iter()
+			.map(|entry| entry.find_word(&words).expect("Checked when parsing") + 1)
+			.sum()
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_last_section(input, factor))
+	}
+}
+
+/* The encodings the puzzle uses, and those seen in dumps and mail bodies */
+pub const CANDIDATES: [Encoding; 7] = [
+	Encoding::Utf8,
+	Encoding::Latin1,
+	Encoding::Utf16Le,
+	Encoding::Utf16Be,
+	Encoding::Utf32Le,
+	Encoding::Utf32Be,
+	Encoding::Utf7,
+];
+
+/* Legacy encodings for Japanese, Chinese and Korean text. These decode most
+ * byte strings to something, so their decodings need to beat the others on
+ * plausibility. */
+pub const EAST_ASIAN: [Encoding; 7] = [
+	Encoding::MultiByte(MultiByte::ShiftJis),
+	Encoding::MultiByte(MultiByte::EucJp),
+	Encoding::MultiByte(MultiByte::Iso2022Jp),
+	Encoding::MultiByte(MultiByte::Gbk),
+	Encoding::MultiByte(MultiByte::Gb18030),
+	Encoding::MultiByte(MultiByte::Big5),
+	Encoding::MultiByte(MultiByte::EucKr),
+];
+
+/* Words are letters only. When more than one decoding is, the most plausible
+ * one is taken. Words without such a decoding are left empty, which no
+ * crossword entry matches. */
+fn decode_words(words: &[Vec<u8>]) -> Vec<String> {
+	let encodings = [CANDIDATES.as_slice(), &EAST_ASIAN].concat();
+
+	words
+		.iter()
+		.map(|bytes| {
+			decode_candidates(bytes, &encodings)
+				.filter(|word| word.chars().all(char::is_alphabetic))
+				.min_by(|a, b| detect::plausibility(b).total_cmp(&detect::plausibility(a)))
+				.unwrap_or_default()
+		})
+		.collect()
+}
+
+/* Different encodings often agree, so every distinct decoding is only
+ * returned once */
+pub fn decode_candidates(bytes: &[u8], encodings: &[Encoding]) -> impl Iterator<Item = String> {
+	encodings
+		.iter()
+		.filter_map(|encoding| encoding.decode(bytes).ok())
+		.unique()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode_candidates() {
+		let bytes = "日本"
+			.encode_utf16()
+			.flat_map(u16::to_be_bytes)
+			.collect::<Vec<_>>();
+		assert_eq!(
+			decode_candidates(&bytes, &CANDIDATES).last().as_deref(),
+			Some("日本")
+		);
+
+		let candidates = [CANDIDATES.as_slice(), &EAST_ASIAN].concat();
+		assert!(decode_candidates(b"\xc6\xfc\xcb\xdc", &candidates).any(|word| word == "日本"));
+	}
+
+	#[test]
+	fn test_solve_east_asian() {
+		/* kaffé in UTF-8 and 日本語 in Shift_JIS */
+		let input = Day13::parse("6b616666c3a9\n93fa967b8cea\n\n.本.\n..f..\n").unwrap();
+		assert_eq!(Day13::solve(&input), 2 + 1);
+	}
+
+	#[test]
+	fn test_parse_error() {
+		let err = Day13::parse("6b616666c3a9\n\n...\n").unwrap_err();
+		assert_eq!((err.line, err.column), (3, 1));
+		assert_eq!(err.message, "Expected one word to fit ..., found 0");
+
+		let err = Day13::parse("6b616666c3a9\n6b616666e9\n\n.a...\n").unwrap_err();
+		assert_eq!(err.message, "Expected one word to fit .a..., found 2");
+	}
+}
@@ -0,0 +1,170 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use itertools::{Itertools, MinMaxResult};
+use rayon::iter::{IntoParallelRefIterator as _, ParallelIterator as _};
+
+use crate::{
+	InputError, Puzzle,
+	error::{Line, numbered_lines},
+	puzzle::repeat_last_section,
+};
+
+const YEAR: i32 = 2022;
+
+const WORK_WINDOW: [NaiveTime; 2] = [
+	NaiveTime::from_hms_opt(8, 30, 0).unwrap(),
+	NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+];
+
+const WHOLE_DAY: [NaiveTime; 2] = [NaiveTime::MIN, NaiveTime::from_hms_opt(23, 59, 59).unwrap()];
+
+pub struct Day15;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+	pub offices: Vec<Location>,
+	pub customers: Vec<Location>,
+}
+
+impl Puzzle for Day15 {
+	type Input = Input;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let mut lines = numbered_lines(input);
+
+		let offices = (&mut lines)
+			.take_while(|line| !line.text.is_empty())
+			.map(|line| {
+				parse_entry(line).map(|(timezone, holidays)| Location {
+					timezone,
+					holidays,
+					time_range: WORK_WINDOW[0]..WORK_WINDOW[1],
+				})
+			})
+			.collect::<Result<_, _>>()?;
+
+		let customers = lines
+			.map(|line| {
+				parse_entry(line).map(|(timezone, holidays)| Location {
+					timezone,
+					holidays,
+					time_range: WHOLE_DAY[0]..WHOLE_DAY[1],
+				})
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Input { offices, customers })
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let offices = &input.offices;
+
+		match input
+			.customers
+			.par_iter()
+			.map(|customer| {
+				all_minutes_in_year()
+					.filter(|minute| {
+						customer.supports(*minute)
+							&& !offices.iter().any(|office| office.supports(*minute))
+					})
+					.count()
+			})
+			.collect::<Vec<_>>()
+			.into_iter()
+			.minmax()
+		{
+			MinMaxResult::MinMax(min, max) => max - min,
+			_ => 0,
+		}
+	}
+
+	fn scale(input: &str, factor: usize) -> Option<String> {
+		Some(repeat_last_section(input, factor))
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+	pub timezone: Tz,
+	pub holidays: Vec<NaiveDate>,
+	pub time_range: Range<NaiveTime>,
+}
+
+impl Location {
+	pub fn supports(&self, minute: DateTime<Utc>) -> bool {
+		let local = minute.with_timezone(&self.timezone);
+		let date = local.date_naive();
+		let time = local.time();
+
+		is_weekday(date) && self.time_range.contains(&time) && !self.holidays.contains(&date)
+	}
+}
+
+pub fn all_minutes_in_year() -> impl Iterator<Item = DateTime<Utc>> {
+	(0..)
+		.map(|i| {
+			let base = Utc.with_ymd_and_hms(YEAR, 1, 1, 0, 0, 0).unwrap();
+			base + TimeDelta::minutes(i)
+		})
+		.take_while(|date| date.year() == YEAR)
+}
+
+pub type InputEntry = (Tz, Vec<NaiveDate>);
+
+pub fn parse_entry(line: Line) -> Result<InputEntry, InputError> {
+	fn split_once<'a>(
+		line: &Line,
+		str: &'a str,
+		delimiter: &str,
+		what: &str,
+	) -> Result<(&'a str, &'a str), InputError> {
+		str.split_once(delimiter)
+			.ok_or_else(|| line.error(str, format!("Expected {what}")))
+	}
+
+	/* Don't care about the name */
+	let (_, rest) = split_once(&line, line.text, "\t", "a tab after the name")?;
+	let (tz, holidays) = split_once(&line, rest, "\t", "a tab after the timezone")?;
+	let tz: Tz = line.parse(tz)?;
+	let holidays = holidays
+		.split(";")
+		.map(|date| parse_date(&line, date))
+		.collect::<Result<_, _>>()?;
+
+	Ok((tz, holidays))
+}
+
+/* `date` is a slice of `line`, so errors can point at the offending part. */
+pub fn parse_date(line: &Line, date: &str) -> Result<NaiveDate, InputError> {
+	let mut parts = date.splitn(3, " ");
+	let (Some(day), Some(month), Some(year)) = (parts.next(), parts.next(), parts.next()) else {
+		return Err(line.error(date, "Expected a date like '1 January 2022'"));
+	};
+
+	let month_number = match month {
+		"January" => 1,
+		"February" => 2,
+		"March" => 3,
+		"April" => 4,
+		"May" => 5,
+		"June" => 6,
+		"July" => 7,
+		"August" => 8,
+		"September" => 9,
+		"October" => 10,
+		"November" => 11,
+		"December" => 12,
+		other => return Err(line.error(month, format!("Unknown month {other}"))),
+	};
+
+	NaiveDate::from_ymd_opt(line.parse(year)?, month_number, line.parse(day)?)
+		.ok_or_else(|| line.error(date, "Invalid date"))
+}
+
+pub fn is_weekday(date: NaiveDate) -> bool {
+	!matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
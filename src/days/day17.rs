@@ -0,0 +1,372 @@
+use core::str;
+use std::{borrow::Cow, fmt::Debug};
+
+use itertools::Itertools;
+
+use crate::{InputError, Puzzle, bytes::parse_hex_spans, error::numbered_lines, utf8};
+
+const TOP_LEFT: [u8; 3] = [0xe2, 0x95, 0x94];
+const BOTTOM_LEFT: [u8; 3] = [0xe2, 0x95, 0x9a];
+
+pub type MapKeys = Vec<Vec<(u32, u32)>>;
+
+pub struct Day17;
+
+impl Puzzle for Day17 {
+	type Input = Vec<MapPiece>;
+	type Answer = usize;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError> {
+		let mut bytes = vec![vec![]];
+		let mut width = None;
+
+		for line in numbered_lines(input) {
+			if line.text.is_empty() {
+				bytes.push(vec![]);
+				continue;
+			}
+
+			let (row, spans) = parse_hex_spans(line)?;
+			if let Err(err) = utf8::window(&row) {
+				let start = spans[err.offset].start;
+				let end = spans[err.offset + err.len - 1].end;
+				return Err(line.error_at_offset(start, &line.text[start..end], err));
+			}
+			if *width.get_or_insert(row.len()) != row.len() {
+				return Err(line.error(
+					line.text,
+					format!("Expected {} bytes like the first row", width.unwrap()),
+				));
+			}
+
+			bytes.last_mut().unwrap().push(row);
+		}
+
+		let pieces = bytes
+			.into_iter()
+			.filter(|bytes| !bytes.is_empty())
+			.map(|bytes| MapPiece { bytes })
+			.collect::<Vec<_>>();
+
+		if !pieces.iter().any(MapPiece::is_top_left) {
+			return Err(InputError::at_end(
+				input,
+				"Expected a piece with the top left corner",
+			));
+		}
+
+		let Some(map) = assemble(&pieces) else {
+			return Err(InputError::at_end(
+				input,
+				"Expected the pieces to fit together into a map",
+			));
+		};
+		if find_mark(&map).is_none() {
+			return Err(InputError::at_end(input, "Expected the map to contain a ╳"));
+		}
+
+		Ok(pieces)
+	}
+
+	fn solve(input: &Self::Input) -> Self::Answer {
+		let map = assemble(input).expect("Checked when parsing");
+
+		find_mark(&map).expect("Checked when parsing")
+	}
+}
+
+/* The row times the column of the ╳ on the map */
+fn find_mark(map: &[Vec<u8>]) -> Option<usize> {
+	map.iter().enumerate().find_map(|(r, line)| {
+		map_line(line)
+			.chars()
+			.position(|ch| ch == '╳')
+			.map(|c| r * c)
+	})
+}
+
+/* Puts the pieces together, starting from the top left corner. `None` if they
+ * don't fill a whole map. */
+pub fn assemble(map_pieces: &[MapPiece]) -> Option<Vec<Vec<u8>>> {
+	let horizontal_block_size = map_pieces.first()?.width();
+	let vertical_block_size = map_pieces.iter().map(|piece| piece.height()).min()?;
+
+	let map_keys: MapKeys = map_pieces
+		.iter()
+		.map(|piece| {
+			(0..(piece.height() / vertical_block_size))
+				.map(|i| piece.keys(i, vertical_block_size))
+				.collect()
+		})
+		.collect();
+
+	let map_width = map_pieces.iter().filter(|piece| piece.is_top()).count() + 2;
+
+	let map_height = map_pieces
+		.iter()
+		.filter(|piece| piece.is_top_left() || piece.is_left() || piece.is_bottom_left())
+		.map(|piece| piece.height())
+		.sum::<usize>()
+		/ vertical_block_size;
+
+	let mut filled = FilledPieces::new(map_width, map_height);
+
+	{
+		let (top_left_index, top_left) = map_pieces
+			.iter()
+			.enumerate()
+			.find(|(_, piece)| piece.is_top_left())?;
+		filled.set(
+			0,
+			0,
+			top_left_index,
+			top_left.height() / vertical_block_size,
+		);
+	}
+
+	if !filled.solve(&map_keys) {
+		return None;
+	}
+
+	let mut map: Vec<Vec<u8>> =
+		vec![vec![0; map_width * horizontal_block_size]; map_height * vertical_block_size];
+	for (r, c) in filled.indices() {
+		let (piece_index, piece_index_offset) =
+			filled.key_index(r, c).expect("Every cell is filled");
+		let piece = &map_pieces[piece_index];
+
+		for r2 in 0..vertical_block_size {
+			for c2 in 0..horizontal_block_size {
+				let b = piece.bytes[r2 + piece_index_offset * vertical_block_size][c2];
+				map[r * vertical_block_size + r2][c * horizontal_block_size + c2] = b;
+			}
+		}
+	}
+
+	Some(map)
+}
+
+pub fn map_line(line: &[u8]) -> Cow<'_, str> {
+	utf8::decode_lossy(line).text
+}
+
+pub fn print_piece(piece: &MapPiece) {
+	for row in piece.bytes.iter() {
+		println!("{}", utf8::decode_lossy(row).text);
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapPiece {
+	pub bytes: Vec<Vec<u8>>,
+}
+
+impl MapPiece {
+	pub fn keys(&self, block_index: usize, block_size: usize) -> (u32, u32) {
+		/* Two bits per row: the dangling bytes on the left, and the bytes still
+		 * missing on the right */
+		let key = |side: fn(&utf8::Window) -> usize| {
+			self.bytes
+				.iter()
+				.skip(block_index * block_size)
+				.take(block_size)
+				.map(|row| side(&utf8::window(row).expect("Rows are validated when parsing")))
+				.enumerate()
+				.fold(0, |acc, (i, n)| acc | ((n & 0b11) << (i * 2)) as u32)
+		};
+
+		(
+			key(|window| window.prefix.len()),
+			key(|window| window.missing),
+		)
+	}
+
+	pub fn is_top_left(&self) -> bool {
+		self.bytes
+			.first()
+			.map(|row| row.windows(TOP_LEFT.len()).next() == Some(&TOP_LEFT))
+			.unwrap_or(false)
+	}
+
+	pub fn is_bottom_left(&self) -> bool {
+		self.bytes
+			.last()
+			.map(|row| row.windows(BOTTOM_LEFT.len()).next() == Some(&BOTTOM_LEFT))
+			.unwrap_or(false)
+	}
+
+	pub fn is_top(&self) -> bool {
+		self.bytes
+			.first()
+			.and_then(|row| str::from_utf8(row).ok())
+			.map(|row| row.chars().all(|ch| matches!(ch, '-' | '═')))
+			.unwrap_or(false)
+	}
+
+	pub fn is_left(&self) -> bool {
+		self.bytes
+			.iter()
+			.all(|row| matches!(&row[..], &[b'|', ..] | &[0xe2, 0x95, 0x91, ..]))
+	}
+
+	pub fn width(&self) -> usize {
+		self.bytes.first().map(|row| row.len()).unwrap_or(0)
+	}
+
+	pub fn height(&self) -> usize {
+		self.bytes.len()
+	}
+}
+
+pub struct FilledPieces {
+	filled: Vec<Option<usize>>,
+	width: usize,
+	height: usize,
+}
+
+impl FilledPieces {
+	pub fn new(width: usize, height: usize) -> Self {
+		FilledPieces {
+			filled: vec![None; width * height],
+			width,
+			height,
+		}
+	}
+
+	pub fn get(&self, row: usize, column: usize) -> Option<usize> {
+		self.filled[self.idx(row, column)?]
+	}
+
+	pub fn set(&mut self, row: usize, column: usize, index: usize, height: usize) -> bool {
+		if !self.in_range(row + height - 1, column) {
+			return false;
+		}
+
+		for r in row..(row + height) {
+			let idx = self.idx(r, column).unwrap();
+			self.filled[idx] = Some(index);
+		}
+
+		true
+	}
+
+	fn in_range(&self, row: usize, column: usize) -> bool {
+		row < self.height && column < self.width
+	}
+
+	fn idx(&self, row: usize, column: usize) -> Option<usize> {
+		self.in_range(row, column)
+			.then_some(row * self.width + column)
+	}
+
+	pub fn space_for(&self, row: usize, column: usize) -> usize {
+		(row..self.height)
+			.take_while(|r| self.get(*r, column).is_none())
+			.count()
+	}
+
+	pub fn indices(&self) -> impl Iterator<Item = (usize, usize)> {
+		(0..self.height).flat_map(|r| (0..self.width).map(move |c| (r, c)))
+	}
+
+	pub fn key_index(&self, row: usize, column: usize) -> Option<(usize, usize)> {
+		let v = self.get(row, column)?;
+		Some((
+			v,
+			(0..row)
+				.rev()
+				.take_while(|r| self.get(*r, column) == Some(v))
+				.count(),
+		))
+	}
+
+	pub fn solve(&mut self, keys: &MapKeys) -> bool {
+		fn key_used(filled_pieces: &FilledPieces, index: usize) -> bool {
+			filled_pieces.filled.iter().contains(&Some(index))
+		}
+
+		'outer: loop {
+			if self.filled.iter().all(|x| x.is_some()) {
+				return true;
+			}
+
+			for (row, column) in self.indices().collect::<Vec<_>>() {
+				// Uggh
+				for (key_index, key) in keys.iter().enumerate() {
+					if self.space_for(row, column) < key.len() || key_used(self, key_index) {
+						continue;
+					}
+
+					let fit_left = if column > 0 {
+						(row..row + key.len()).enumerate().any(|(i, r)| {
+							self.key_index(r, column - 1)
+								.is_some_and(|(k, ki)| keys[k][ki].1 == key[i].0)
+						})
+					} else {
+						false
+					};
+
+					let fit_right = if column < self.width - 1 {
+						(row..row + key.len()).enumerate().any(|(i, r)| {
+							self.key_index(r, column + 1)
+								.is_some_and(|(k, ki)| keys[k][ki].0 == key[i].1)
+						})
+					} else {
+						false
+					};
+
+					if fit_left || fit_right {
+						self.set(row, column, key_index, key.len());
+						continue 'outer;
+					}
+				}
+			}
+
+			return false;
+		}
+	}
+}
+
+impl Debug for FilledPieces {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		writeln!(f, "{}x{}", self.width, self.height)?;
+		for r in 0..self.height {
+			for c in 0..self.width {
+				if let Some(i) = self.get(r, c) {
+					write!(f, "{i:4} ")?;
+				} else {
+					write!(f, "     ")?;
+				}
+			}
+
+			writeln!(f)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXAMPLE: &str = include_str!("../../tests/fixtures/17/example.input");
+
+	#[test]
+	fn test_parse_error() {
+		let err = Day17::parse("e29594\n").unwrap_err();
+		assert_eq!(
+			err.message,
+			"Expected the pieces to fit together into a map"
+		);
+
+		let err = Day17::parse("2d2de29590\n").unwrap_err();
+		assert_eq!(err.message, "Expected a piece with the top left corner");
+
+		/* ╳ replaced by ═, which is as long */
+		let err = Day17::parse(&EXAMPLE.replace("e295b3", "e29590")).unwrap_err();
+		assert_eq!(err.message, "Expected the map to contain a ╳");
+
+		assert!(Day17::parse(EXAMPLE).is_ok());
+	}
+}
@@ -0,0 +1,515 @@
+pub mod detect;
+mod index;
+mod labels;
+pub mod mojibake;
+mod multi_byte;
+mod single_byte;
+pub mod stream;
+pub mod transcode;
+mod utf7;
+
+use std::{char::decode_utf16, fmt::Display};
+
+pub use labels::LABELS;
+pub use multi_byte::{Decoder, Encoder, MultiByte};
+pub use single_byte::CodePage;
+
+use crate::utf8::{self, Utf8Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+	Utf8,
+	/* Every byte is the code point with the same value */
+	Latin1,
+	Utf16Le,
+	Utf16Be,
+	Utf32Le,
+	Utf32Be,
+	/* UTF-16 in modified base64, for 7-bit channels like mail */
+	Utf7,
+	SingleByte(CodePage),
+	MultiByte(MultiByte),
+}
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+	#[error(transparent)]
+	Utf8(#[from] Utf8Error),
+	#[error("Incomplete code unit at byte {offset}")]
+	Truncated { offset: usize },
+	#[error("Unpaired surrogate {unit:#06X} at byte {offset}")]
+	UnpairedSurrogate { offset: usize, unit: u16 },
+	#[error("Found a {found} byte order mark while decoding {expected}")]
+	ByteOrderMark { expected: Encoding, found: Encoding },
+	#[error("Byte {byte:#04X} at byte {offset} is undefined in {encoding}")]
+	Undefined {
+		offset: usize,
+		byte: u8,
+		encoding: Encoding,
+	},
+	#[error("Invalid code point {value:#X} at byte {offset}")]
+	InvalidCodePoint { offset: usize, value: u32 },
+	#[error("Malformed {encoding} sequence at byte {offset}")]
+	Malformed { offset: usize, encoding: Encoding },
+}
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("'{ch}' at byte {offset} can't be encoded in {encoding}")]
+pub struct EncodeError {
+	pub offset: usize,
+	pub ch: char,
+	pub encoding: Encoding,
+}
+
+impl Encoding {
+	/* The encodings that have a byte order mark. UTF-32LE comes before
+	 * UTF-16LE, as its byte order mark starts with that of UTF-16LE. */
+	pub const UNICODE: [Encoding; 5] = [
+		Encoding::Utf8,
+		Encoding::Utf32Le,
+		Encoding::Utf16Le,
+		Encoding::Utf16Be,
+		Encoding::Utf32Be,
+	];
+
+	/* Looks up a WHATWG or IANA label like `latin1` or `Windows-1252` */
+	pub fn for_label(label: &str) -> Option<Encoding> {
+		labels::lookup(label)
+	}
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Encoding::Utf8 => "UTF-8",
+			Encoding::Latin1 => "ISO-8859-1",
+			Encoding::Utf16Le => "UTF-16LE",
+			Encoding::Utf16Be => "UTF-16BE",
+			Encoding::Utf32Le => "UTF-32LE",
+			Encoding::Utf32Be => "UTF-32BE",
+			Encoding::Utf7 => "UTF-7",
+			Encoding::SingleByte(code_page) => code_page.name(),
+			Encoding::MultiByte(multi_byte) => multi_byte.name(),
+		}
+	}
+
+	pub const fn bom(self) -> Option<&'static [u8]> {
+		match self {
+			Encoding::Utf8 => Some(&[0xEF, 0xBB, 0xBF]),
+			Encoding::Utf16Le => Some(&[0xFF, 0xFE]),
+			Encoding::Utf16Be => Some(&[0xFE, 0xFF]),
+			Encoding::Utf32Le => Some(&[0xFF, 0xFE, 0x00, 0x00]),
+			Encoding::Utf32Be => Some(&[0x00, 0x00, 0xFE, 0xFF]),
+			/* UTF-7 has one, but it isn't byte aligned, so it's stripped after
+			 * decoding instead */
+			Encoding::Utf7
+			| Encoding::Latin1
+			| Encoding::SingleByte(_)
+			| Encoding::MultiByte(_) => None,
+		}
+	}
+
+	/* Returns the encoding whose byte order mark `bytes` starts with */
+	pub fn sniff(bytes: &[u8]) -> Option<Encoding> {
+		Self::UNICODE
+			.into_iter()
+			.find(|encoding| encoding.bom().is_some_and(|bom| bytes.starts_with(bom)))
+	}
+
+	/* A leading byte order mark for this encoding is skipped, one for another
+	 * Unicode encoding is an error rather than being decoded as text. That
+	 * keeps e.g. UTF-32LE from passing as UTF-16LE starting with a NUL.
+	 * Encodings without a byte order mark decode it like any bytes. */
+	pub fn decode(self, input: &[u8]) -> Result<String, DecodeError> {
+		let bytes = self.strip_bom(input)?;
+		let result = self.decode_without_bom(bytes).map(|text| match self {
+			/* UTF-7's byte order mark can only be recognised once decoded */
+			Encoding::Utf7 => text
+				.strip_prefix('\u{FEFF}')
+				.map(String::from)
+				.unwrap_or(text),
+			_ => text,
+		});
+
+		/* Report offsets into the input, including the byte order mark */
+		result.map_err(|err| err.offset_by(input.len() - bytes.len()))
+	}
+
+	/* Like `decode`, but a byte order mark overrides this encoding */
+	pub fn decode_with_bom(self, bytes: &[u8]) -> Result<(Encoding, String), DecodeError> {
+		let encoding = Self::sniff(bytes).unwrap_or(self);
+		Ok((encoding, encoding.decode(bytes)?))
+	}
+
+	fn strip_bom(self, input: &[u8]) -> Result<&[u8], DecodeError> {
+		match Self::sniff(input) {
+			Some(_) if self.bom().is_none() => Ok(input),
+			Some(found) if found == self => Ok(&input[self.bom().unwrap().len()..]),
+			Some(found) => Err(DecodeError::ByteOrderMark {
+				expected: self,
+				found,
+			}),
+			None => Ok(input),
+		}
+	}
+
+	/* Decodes all of `bytes` as text, byte order mark or not */
+	fn decode_without_bom(self, bytes: &[u8]) -> Result<String, DecodeError> {
+		match self {
+			Encoding::Utf8 => utf8::validate(bytes)
+				.map(String::from)
+				.map_err(|errors| DecodeError::Utf8(errors[0])),
+			Encoding::Latin1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+			Encoding::Utf16Le => decode_utf16_units(bytes, u16::from_le_bytes),
+			Encoding::Utf16Be => decode_utf16_units(bytes, u16::from_be_bytes),
+			Encoding::Utf32Le => decode_utf32_units(bytes, u32::from_le_bytes),
+			Encoding::Utf32Be => decode_utf32_units(bytes, u32::from_be_bytes),
+			Encoding::Utf7 => utf7::decode(bytes),
+			Encoding::SingleByte(code_page) => bytes
+				.iter()
+				.enumerate()
+				.map(|(offset, &byte)| {
+					code_page.decode_byte(byte).ok_or(DecodeError::Undefined {
+						offset,
+						byte,
+						encoding: self,
+					})
+				})
+				.collect(),
+			Encoding::MultiByte(multi_byte) => {
+				let mut text = String::with_capacity(bytes.len());
+				Decoder::new(multi_byte)
+					.decode(bytes, true, &mut text)
+					.map(|()| text)
+			}
+		}
+	}
+
+	/* Byte order marks aren't added, prepend `bom()` for that */
+	pub fn encode(self, text: &str) -> Result<Vec<u8>, EncodeError> {
+		self.encode_with(text, Err)
+	}
+
+	/* Like `encode`, but characters the encoding has no bytes for are passed to
+	 * `fallback`, and what it returns is encoded in their place */
+	pub fn encode_with(
+		self,
+		text: &str,
+		mut fallback: impl FnMut(EncodeError) -> Result<String, EncodeError>,
+	) -> Result<Vec<u8>, EncodeError> {
+		/* UTF-7 encodes runs of characters together, and has no unmappables */
+		if self == Encoding::Utf7 {
+			return Ok(utf7::encode(text));
+		}
+
+		let mut bytes = Vec::with_capacity(text.len());
+		let mut encoder = match self {
+			Encoding::MultiByte(multi_byte) => Some(Encoder::new(multi_byte)),
+			_ => None,
+		};
+
+		for (offset, ch) in text.char_indices() {
+			if self.encode_char(ch, encoder.as_mut(), &mut bytes) {
+				continue;
+			}
+
+			let error = EncodeError {
+				offset,
+				ch,
+				encoding: self,
+			};
+			for substitute in fallback(error)?.chars() {
+				if !self.encode_char(substitute, encoder.as_mut(), &mut bytes) {
+					return Err(error);
+				}
+			}
+		}
+
+		if let Some(encoder) = &mut encoder {
+			encoder.finish(&mut bytes);
+		}
+
+		Ok(bytes)
+	}
+
+	fn encode_char(self, ch: char, encoder: Option<&mut Encoder>, bytes: &mut Vec<u8>) -> bool {
+		match self {
+			Encoding::Utf8 => bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+			Encoding::Latin1 => match u8::try_from(ch) {
+				Ok(b) => bytes.push(b),
+				Err(_) => return false,
+			},
+			Encoding::Utf16Le | Encoding::Utf16Be => {
+				for unit in ch.encode_utf16(&mut [0; 2]) {
+					bytes.extend_from_slice(&match self {
+						Encoding::Utf16Le => unit.to_le_bytes(),
+						_ => unit.to_be_bytes(),
+					});
+				}
+			}
+			Encoding::Utf32Le => bytes.extend_from_slice(&(ch as u32).to_le_bytes()),
+			Encoding::Utf32Be => bytes.extend_from_slice(&(ch as u32).to_be_bytes()),
+			Encoding::Utf7 => unreachable!(),
+			Encoding::SingleByte(code_page) => match code_page.encode_char(ch) {
+				Some(b) => bytes.push(b),
+				None => return false,
+			},
+			Encoding::MultiByte(_) => return encoder.unwrap().encode_char(ch, bytes),
+		}
+
+		true
+	}
+}
+
+impl Display for Encoding {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}
+}
+
+impl DecodeError {
+	fn offset_by(self, by: usize) -> Self {
+		match self {
+			DecodeError::Utf8(err) => DecodeError::Utf8(Utf8Error {
+				offset: err.offset + by,
+				..err
+			}),
+			DecodeError::Truncated { offset } => DecodeError::Truncated {
+				offset: offset + by,
+			},
+			DecodeError::UnpairedSurrogate { offset, unit } => DecodeError::UnpairedSurrogate {
+				offset: offset + by,
+				unit,
+			},
+			DecodeError::Undefined {
+				offset,
+				byte,
+				encoding,
+			} => DecodeError::Undefined {
+				offset: offset + by,
+				byte,
+				encoding,
+			},
+			DecodeError::InvalidCodePoint { offset, value } => DecodeError::InvalidCodePoint {
+				offset: offset + by,
+				value,
+			},
+			DecodeError::Malformed { offset, encoding } => DecodeError::Malformed {
+				offset: offset + by,
+				encoding,
+			},
+			err @ DecodeError::ByteOrderMark { .. } => err,
+		}
+	}
+}
+
+fn decode_utf16_units(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Result<String, DecodeError> {
+	let chunks = bytes.chunks_exact(2);
+	if !chunks.remainder().is_empty() {
+		return Err(DecodeError::Truncated {
+			offset: bytes.len() - 1,
+		});
+	}
+
+	let mut offset = 0;
+	decode_utf16(chunks.map(|chunk| unit([chunk[0], chunk[1]])))
+		.map(|result| {
+			let ch = result.map_err(|err| DecodeError::UnpairedSurrogate {
+				offset,
+				unit: err.unpaired_surrogate(),
+			})?;
+			offset += ch.len_utf16() * 2;
+			Ok(ch)
+		})
+		.collect()
+}
+
+fn decode_utf32_units(bytes: &[u8], unit: fn([u8; 4]) -> u32) -> Result<String, DecodeError> {
+	let chunks = bytes.chunks_exact(4);
+	if !chunks.remainder().is_empty() {
+		return Err(DecodeError::Truncated {
+			offset: bytes.len() - chunks.remainder().len(),
+		});
+	}
+
+	chunks
+		.enumerate()
+		.map(|(i, chunk)| {
+			let value = unit(chunk.try_into().unwrap());
+			char::from_u32(value).ok_or(DecodeError::InvalidCodePoint {
+				offset: i * 4,
+				value,
+			})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode() {
+		assert_eq!(
+			Encoding::Utf8.decode(b"\xEF\xBB\xBFh\xC3\xA9"),
+			Ok("hé".to_string())
+		);
+		assert_eq!(Encoding::Latin1.decode(b"h\xE9"), Ok("hé".to_string()));
+		assert_eq!(
+			Encoding::Utf16Le.decode(b"\xFF\xFEh\x00\xE9\x00"),
+			Ok("hé".to_string())
+		);
+		assert_eq!(
+			Encoding::Utf16Be.decode(b"\x00h\x00\xE9"),
+			Ok("hé".to_string())
+		);
+		assert_eq!(
+			Encoding::Utf16Be.decode(b"\xD8\x3D\xDE\x00"),
+			Ok("😀".to_string())
+		);
+	}
+
+	#[test]
+	fn test_decode_errors() {
+		assert_eq!(
+			Encoding::Utf16Le.decode(b"\xFF\xFEa\x00b"),
+			Err(DecodeError::Truncated { offset: 4 })
+		);
+		assert_eq!(
+			Encoding::Utf16Le.decode(b"\xFF\xFEa\x00\x3D\xD8a\x00"),
+			Err(DecodeError::UnpairedSurrogate {
+				offset: 4,
+				unit: 0xD83D
+			})
+		);
+		assert_eq!(
+			Encoding::Utf16Le.decode(b"\xFE\xFF\x00a"),
+			Err(DecodeError::ByteOrderMark {
+				expected: Encoding::Utf16Le,
+				found: Encoding::Utf16Be
+			})
+		);
+		assert!(matches!(
+			Encoding::Utf8.decode(b"\xEF\xBB\xBFa\xFF"),
+			Err(DecodeError::Utf8(Utf8Error { offset: 4, .. }))
+		));
+	}
+
+	#[test]
+	fn test_single_byte() {
+		let koi8r = Encoding::SingleByte(CodePage::Koi8R);
+		assert_eq!(
+			koi8r.decode(b"\xF0\xD2\xC9\xD7\xC5\xD4"),
+			Ok("Привет".to_string())
+		);
+		assert_eq!(
+			koi8r.encode("Привет"),
+			Ok(b"\xF0\xD2\xC9\xD7\xC5\xD4".to_vec())
+		);
+
+		/* The UTF-8 byte order mark is just text in a code page */
+		let windows1252 = Encoding::SingleByte(CodePage::Windows1252);
+		assert_eq!(
+			windows1252.decode(b"\xEF\xBB\xBF\x80"),
+			Ok("ï»¿€".to_string())
+		);
+
+		assert_eq!(
+			Encoding::SingleByte(CodePage::Iso8859_3).decode(b"ab\xA5"),
+			Err(DecodeError::Undefined {
+				offset: 2,
+				byte: 0xA5,
+				encoding: Encoding::SingleByte(CodePage::Iso8859_3)
+			})
+		);
+		assert_eq!(
+			Encoding::Latin1.encode("a€"),
+			Err(EncodeError {
+				offset: 1,
+				ch: '€',
+				encoding: Encoding::Latin1
+			})
+		);
+	}
+
+	#[test]
+	fn test_multi_byte() {
+		let shift_jis = Encoding::for_label("sjis").unwrap();
+		assert_eq!(shift_jis, Encoding::MultiByte(MultiByte::ShiftJis));
+		assert_eq!(shift_jis.encode("日本"), Ok(b"\x93\xfa\x96\x7b".to_vec()));
+		assert_eq!(
+			shift_jis.decode(b"\x93\xfa\x96\x7b"),
+			Ok("日本".to_string())
+		);
+		assert_eq!(
+			Encoding::MultiByte(MultiByte::Iso2022Jp).encode("日本"),
+			Ok(b"\x1b$BF|K\\\x1b(B".to_vec())
+		);
+	}
+
+	#[test]
+	fn test_for_label() {
+		assert_eq!(Encoding::for_label(" Latin1 "), Some(Encoding::Latin1));
+		assert_eq!(
+			Encoding::for_label("windows-1251"),
+			Some(Encoding::SingleByte(CodePage::Windows1251))
+		);
+		assert_eq!(
+			Encoding::for_label("CP437"),
+			Some(Encoding::SingleByte(CodePage::Ibm437))
+		);
+		assert_eq!(Encoding::for_label("utf-9"), None);
+
+		/* Every encoding can be found by its own name */
+		for (_, encoding) in LABELS {
+			assert_eq!(Encoding::for_label(encoding.name()), Some(*encoding));
+		}
+	}
+
+	#[test]
+	fn test_utf32() {
+		assert_eq!(
+			Encoding::Utf32Le.decode(b"\xFF\xFE\x00\x00h\x00\x00\x00\x00\xF6\x01\x00"),
+			Ok("h😀".to_string())
+		);
+		assert_eq!(
+			Encoding::Utf32Be.decode(b"\x00\x00\x00h"),
+			Ok("h".to_string())
+		);
+		assert_eq!(
+			Encoding::Utf32Be.encode("h😀"),
+			Ok(b"\x00\x00\x00h\x00\x01\xF6\x00".to_vec())
+		);
+		assert_eq!(
+			Encoding::Utf32Le.decode(b"\x00\xD8\x00\x00"),
+			Err(DecodeError::InvalidCodePoint {
+				offset: 0,
+				value: 0xD800
+			})
+		);
+		assert_eq!(
+			Encoding::Utf32Be.decode(b"\x00\x00\x00h\x00"),
+			Err(DecodeError::Truncated { offset: 4 })
+		);
+
+		/* Not a UTF-16LE byte order mark followed by U+0000 */
+		let utf32 = b"\xFF\xFE\x00\x00a\x00\x00\x00";
+		assert_eq!(Encoding::sniff(utf32), Some(Encoding::Utf32Le));
+		assert_eq!(
+			Encoding::Utf16Le.decode(utf32),
+			Err(DecodeError::ByteOrderMark {
+				expected: Encoding::Utf16Le,
+				found: Encoding::Utf32Le
+			})
+		);
+		assert_eq!(Encoding::sniff(b"\xFF\xFEa\x00"), Some(Encoding::Utf16Le));
+	}
+
+	#[test]
+	fn test_sniff() {
+		assert_eq!(Encoding::sniff(b"\xFE\xFF\x00a"), Some(Encoding::Utf16Be));
+		assert_eq!(Encoding::sniff(b"abc"), None);
+		assert_eq!(
+			Encoding::Utf8.decode_with_bom(b"\xFF\xFEa\x00"),
+			Ok((Encoding::Utf16Le, "a".to_string()))
+		);
+	}
+}
@@ -0,0 +1,690 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::OnceLock,
+};
+
+use super::{CodePage, Encoding, MultiByte, stream::StreamDecoder};
+
+/* One way of decoding the input, with how plausible the text looks. The
+ * confidences of all candidates add up to 1. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+	pub encoding: Encoding,
+	pub text: String,
+	pub plausibility: f64,
+	pub confidence: f64,
+}
+
+/* Below this, the best candidate isn't much more likely than the next one */
+pub const AMBIGUOUS_CONFIDENCE: f64 = 0.8;
+
+/* Encodings worth telling apart when nothing is known about the input. The
+ * single-byte code pages decode anything, so one of them always fits. */
+pub const COMMON: [Encoding; 12] = [
+	Encoding::Utf8,
+	Encoding::Utf16Le,
+	Encoding::Utf16Be,
+	Encoding::SingleByte(CodePage::Windows1252),
+	Encoding::SingleByte(CodePage::Windows1250),
+	Encoding::SingleByte(CodePage::Windows1251),
+	Encoding::SingleByte(CodePage::Koi8R),
+	Encoding::MultiByte(MultiByte::ShiftJis),
+	Encoding::MultiByte(MultiByte::EucJp),
+	Encoding::MultiByte(MultiByte::Gb18030),
+	Encoding::MultiByte(MultiByte::Big5),
+	Encoding::MultiByte(MultiByte::EucKr),
+];
+
+/* The encoding announced by the byte order mark, or else the most plausible of
+ * `COMMON` */
+pub fn guess(bytes: &[u8]) -> Encoding {
+	Encoding::sniff(bytes)
+		.or_else(|| Some(rank(bytes, &COMMON).first()?.encoding))
+		.unwrap_or(Encoding::Utf8)
+}
+
+/* Like `guess`, for the start of a longer input, which may end halfway
+ * through a sequence */
+pub fn guess_start(bytes: &[u8]) -> Encoding {
+	let decode_start = |encoding, bytes: &[u8]| {
+		let mut text = String::new();
+		StreamDecoder::new(encoding)
+			.decode(bytes, false, &mut text)
+			.ok()
+			.map(|()| text)
+	};
+
+	Encoding::sniff(bytes)
+		.or_else(|| Some(rank_with(bytes, &COMMON, decode_start).first()?.encoding))
+		.unwrap_or(Encoding::Utf8)
+}
+
+/* Decodes `bytes` with every encoding that accepts them and ranks the results,
+ * most plausible first. Encodings that decode to the same text as an earlier
+ * one are left out. */
+pub fn rank(bytes: &[u8], encodings: &[Encoding]) -> Vec<Candidate> {
+	rank_with(bytes, encodings, |encoding, bytes| {
+		encoding.decode(bytes).ok()
+	})
+}
+
+fn rank_with(
+	bytes: &[u8],
+	encodings: &[Encoding],
+	decode: impl Fn(Encoding, &[u8]) -> Option<String>,
+) -> Vec<Candidate> {
+	let mut candidates: Vec<Candidate> = Vec::new();
+
+	for &encoding in encodings {
+		let Some(text) = decode(encoding, bytes) else {
+			continue;
+		};
+
+		if candidates.iter().any(|candidate| candidate.text == text) {
+			continue;
+		}
+
+		candidates.push(Candidate {
+			encoding,
+			plausibility: plausibility(&text),
+			text,
+			confidence: 0.0,
+		});
+	}
+
+	/* Longer texts give more evidence, so they get more decisive confidences */
+	let evidence = candidates
+		.iter()
+		.map(|candidate| candidate.text.chars().count())
+		.min()
+		.unwrap_or(0)
+		.max(1) as f64;
+	let best = candidates
+		.iter()
+		.map(|candidate| candidate.plausibility)
+		.fold(f64::NEG_INFINITY, f64::max);
+	let weights = candidates
+		.iter()
+		.map(|candidate| ((candidate.plausibility - best) * evidence).exp())
+		.collect::<Vec<_>>();
+	let total = weights.iter().sum::<f64>();
+
+	for (candidate, weight) in candidates.iter_mut().zip(weights) {
+		candidate.confidence = weight / total;
+	}
+
+	candidates.sort_by(|a, b| b.plausibility.total_cmp(&a.plausibility));
+	candidates
+}
+
+/* The average log probability of a character in `text`, from the letter and
+ * letter pair frequencies of common languages, penalties for control
+ * characters and symbols, and for switching scripts or case within a word.
+ * Higher is more plausible, 0 is as plausible as it gets. */
+pub fn plausibility(text: &str) -> f64 {
+	let chars = text.chars().collect::<Vec<_>>();
+	if chars.is_empty() {
+		return 0.0;
+	}
+
+	let mut total = 0.0;
+	let mut languages = [0.0; LANGUAGES.len()];
+
+	for &ch in &chars {
+		match classify(ch) {
+			Class::Letter(Script::Latin | Script::Cyrillic) => {
+				let lower = ch.to_lowercase().next().unwrap_or(ch);
+				for (sum, (_, frequencies, _)) in languages.iter_mut().zip(LANGUAGES) {
+					*sum += frequencies
+						.iter()
+						.find(|&&(letter, _)| letter == lower)
+						.map_or(UNKNOWN_LETTER, |&(_, percentage)| percentage / 100.0)
+						.ln();
+				}
+			}
+			Class::Letter(Script::Han) if common_han().contains(&ch) => total += 0.003f64.ln(),
+			Class::Letter(script) => total += script.letter_probability().ln(),
+			Class::Digit | Class::Whitespace => total += 0.05f64.ln(),
+			Class::Punctuation => total += 0.02f64.ln(),
+			Class::Typographic => total += 0.005f64.ln(),
+			Class::Symbol => total += 0.0005f64.ln(),
+			Class::Invalid => total += 1e-8f64.ln(),
+		}
+	}
+
+	for pair in chars.windows(2) {
+		let (a, b) = (pair[0], pair[1]);
+		total += match (classify(a), classify(b)) {
+			(Class::Letter(x), Class::Letter(y)) if !x.mixes_with(y) => 1e-3f64.ln(),
+			(Class::Letter(_), Class::Letter(_)) if a.is_lowercase() && b.is_uppercase() => {
+				0.01f64.ln()
+			}
+			(Class::Letter(_), Class::Symbol) | (Class::Symbol, Class::Letter(_)) => 0.01f64.ln(),
+			_ => 0.0,
+		};
+
+		let lower = |ch: char| ch.to_lowercase().next().unwrap_or(ch);
+		let (a, b) = (lower(a), lower(b));
+		let common_in = common_pairs().get(&(a, b)).copied().unwrap_or(0);
+		for (i, sum) in languages.iter_mut().enumerate() {
+			if common_in & 1 << i != 0 {
+				*sum += COMMON_PAIR.ln();
+			}
+		}
+	}
+
+	total += languages.into_iter().fold(f64::NEG_INFINITY, f64::max);
+
+	total / chars.len() as f64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Script {
+	Latin,
+	Greek,
+	Cyrillic,
+	Hebrew,
+	Arabic,
+	Thai,
+	Han,
+	Kana,
+	Hangul,
+	Other,
+}
+
+impl Script {
+	pub(crate) fn of(ch: char) -> Script {
+		match ch as u32 {
+			0x41..=0x5A | 0x61..=0x7A | 0xC0..=0x24F | 0x1E00..=0x1EFF => Script::Latin,
+			0x370..=0x3FF | 0x1F00..=0x1FFF => Script::Greek,
+			0x400..=0x52F => Script::Cyrillic,
+			0x590..=0x5FF => Script::Hebrew,
+			0x600..=0x6FF | 0x750..=0x77F => Script::Arabic,
+			0xE00..=0xE7F => Script::Thai,
+			0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x2FFFF => Script::Han,
+			0x3040..=0x30FF | 0xFF66..=0xFF9F => Script::Kana,
+			0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+			_ => Script::Other,
+		}
+	}
+
+	/* Roughly one over the number of letters in common use, for scripts
+	 * without a frequency model. For Han, that's the characters outside of
+	 * `COMMON_HAN`. */
+	fn letter_probability(self) -> f64 {
+		match self {
+			Script::Latin | Script::Cyrillic => UNKNOWN_LETTER,
+			Script::Greek | Script::Hebrew | Script::Arabic | Script::Thai => 0.03,
+			Script::Kana => 0.02,
+			Script::Han => 0.0002,
+			Script::Hangul => 0.003,
+			Script::Other => 0.0001,
+		}
+	}
+
+	/* Japanese mixes kanji and kana, everything else sticks to one script
+	 * within a word */
+	fn mixes_with(self, other: Script) -> bool {
+		self == other && self != Script::Other
+			|| matches!(
+				(self, other),
+				(Script::Han, Script::Kana) | (Script::Kana, Script::Han)
+			)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+	Letter(Script),
+	Digit,
+	Whitespace,
+	Punctuation,
+	/* Non-ASCII punctuation that shows up in real text, like « or — */
+	Typographic,
+	Symbol,
+	/* Control characters, replacement characters and private use */
+	Invalid,
+}
+
+fn classify(ch: char) -> Class {
+	match ch {
+		'\t' | '\n' | '\r' => Class::Whitespace,
+		'\u{FFFD}' | '\u{FEFF}' | '\u{E000}'..='\u{F8FF}' => Class::Invalid,
+		ch if ch.is_control() => Class::Invalid,
+		ch if ch.is_whitespace() => Class::Whitespace,
+		ch if ch.is_numeric() => Class::Digit,
+		ch if ch.is_alphabetic() => Class::Letter(Script::of(ch)),
+		ch if ch.is_ascii_punctuation() => Class::Punctuation,
+		'«' | '»' | '¡' | '¿' | '·' | '\u{2010}'..='\u{2027}' | '\u{3000}'..='\u{303F}' => {
+			Class::Typographic
+		}
+		_ => Class::Symbol,
+	}
+}
+
+/* The most frequent Chinese characters, which make up most of any text, with
+ * traditional forms and characters Japanese uses more often. Mojibake tends to
+ * produce rare characters instead. */
+const COMMON_HAN: &str = "\
+	的一是不了人我在有他这为之大来以个中上们到说国和地也子时道出而要于就下得可你年生自会那后能\
+	对着事其里所去行过家十用发天如然作方成者多日都三小军二无同么经法当起与好看学进种将还分此心前\
+	面又定见只主没公从知使点业本把性应开它合因由些外政四社义平形相全表间样关各重新线内数正反明\
+	原或但质气第向命变条结解问意建月已通并提直题党程展五果料象员革位入常文总次品式活设及管特件\
+	长求老头基资边流路级少图山统接较组计别她手角期根论运农指几九区强放决西被干做必战先回则任取\
+	据处队南给色光门即保治北造百规热领七海口东导器压志世金增争济阶油思术极交受联什认六共权收证\
+	改清己美再采转更单风切打白教速花带安场身车例真务具万每目至达走积示议声报斗完类八离华名确才\
+	科张信马节话米整空元况今集温传土许步群广石记需段研界拉林律叫且究观越织装影算低持音众书布复\
+	容儿须际商非验连断深难近矿千周委素技备半办青省列习响约支般史感劳便团往酸历市克何除消构府称\
+	太准精值号率族维划选标写存候毛亲快效斯院查江型眼王按格养易置派层片始却专状育厂京识适属圆包\
+	火住调满县局照参红细引听该铁价严龙飞\
+	們國來說這為個時會後對過發經當與學進種還見從無點業開應關問題實長門書車話東電語\
+	私円駅社校語週曜氏様";
+
+fn common_han() -> &'static HashSet<char> {
+	static COMMON: OnceLock<HashSet<char>> = OnceLock::new();
+	COMMON.get_or_init(|| COMMON_HAN.chars().collect())
+}
+
+/* For every common pair of letters, the languages it's common in, as a bit
+ * set over `LANGUAGES` */
+fn common_pairs() -> &'static HashMap<(char, char), u8> {
+	static PAIRS: OnceLock<HashMap<(char, char), u8>> = OnceLock::new();
+	PAIRS.get_or_init(|| {
+		let mut common = HashMap::<(char, char), u8>::new();
+		for (i, (_, _, pairs)) in LANGUAGES.iter().enumerate() {
+			for pair in pairs.split(' ') {
+				let mut chars = pair.chars();
+				if let (Some(a), Some(b)) = (chars.next(), chars.next()) {
+					*common.entry((a, b)).or_default() |= 1 << i;
+				}
+			}
+		}
+		common
+	})
+}
+
+/* The probability of a letter the language doesn't use */
+const UNKNOWN_LETTER: f64 = 0.0001;
+
+/* How much likelier a common pair of letters is than its letters on their
+ * own */
+const COMMON_PAIR: f64 = 4.0;
+
+/* Its name, letter frequencies in percent and its most common pairs of
+ * letters */
+type Language = (&'static str, &'static [(char, f64)], &'static str);
+
+const LANGUAGES: [Language; 8] = [
+	(
+		"English",
+		&[
+			('e', 12.7),
+			('t', 9.1),
+			('a', 8.2),
+			('o', 7.5),
+			('i', 7.0),
+			('n', 6.7),
+			('s', 6.3),
+			('h', 6.1),
+			('r', 6.0),
+			('d', 4.3),
+			('l', 4.0),
+			('c', 2.8),
+			('u', 2.8),
+			('m', 2.4),
+			('w', 2.4),
+			('f', 2.2),
+			('g', 2.0),
+			('y', 2.0),
+			('p', 1.9),
+			('b', 1.5),
+			('v', 1.0),
+			('k', 0.8),
+			('j', 0.15),
+			('x', 0.15),
+			('q', 0.1),
+			('z', 0.07),
+		],
+		"th he in er an re on at en nd ti es or te of ed is it al ar st to nt ng se ha as ou",
+	),
+	(
+		"German",
+		&[
+			('e', 16.4),
+			('n', 9.8),
+			('s', 7.3),
+			('r', 7.0),
+			('i', 6.6),
+			('a', 6.5),
+			('t', 6.2),
+			('d', 5.1),
+			('h', 4.6),
+			('u', 4.2),
+			('l', 3.4),
+			('g', 3.0),
+			('c', 2.7),
+			('o', 2.6),
+			('m', 2.5),
+			('b', 1.9),
+			('w', 1.9),
+			('f', 1.7),
+			('k', 1.4),
+			('z', 1.1),
+			('v', 0.8),
+			('p', 0.7),
+			('ü', 0.65),
+			('ä', 0.58),
+			('ö', 0.44),
+			('ß', 0.31),
+			('j', 0.27),
+			('y', 0.04),
+			('x', 0.03),
+			('q', 0.02),
+		],
+		"er en ch de ei te in nd ie ge st ne be es un re an he au ng se it di ic sc",
+	),
+	(
+		"French",
+		&[
+			('e', 14.7),
+			('s', 7.9),
+			('a', 7.6),
+			('i', 7.5),
+			('t', 7.2),
+			('n', 7.1),
+			('r', 6.6),
+			('u', 6.3),
+			('o', 5.8),
+			('l', 5.5),
+			('d', 3.7),
+			('c', 3.3),
+			('p', 3.0),
+			('m', 3.0),
+			('é', 1.9),
+			('v', 1.8),
+			('q', 1.4),
+			('f', 1.1),
+			('b', 0.9),
+			('g', 0.9),
+			('h', 0.7),
+			('j', 0.6),
+			('à', 0.5),
+			('x', 0.4),
+			('è', 0.3),
+			('y', 0.3),
+			('ê', 0.2),
+			('z', 0.1),
+			('ç', 0.1),
+			('œ', 0.02),
+			('ô', 0.05),
+			('û', 0.05),
+			('î', 0.05),
+			('ù', 0.05),
+			('ë', 0.02),
+			('ï', 0.02),
+			('w', 0.05),
+			('k', 0.05),
+		],
+		"es le de en on nt re er te el an se et la ai it me ou em ie ur qu ue ns",
+	),
+	(
+		"Spanish",
+		&[
+			('e', 13.7),
+			('a', 12.5),
+			('o', 8.7),
+			('s', 8.0),
+			('r', 6.9),
+			('n', 6.7),
+			('i', 6.2),
+			('d', 5.9),
+			('l', 5.0),
+			('c', 4.7),
+			('t', 4.6),
+			('u', 3.9),
+			('m', 3.2),
+			('p', 2.5),
+			('b', 1.4),
+			('g', 1.0),
+			('v', 0.9),
+			('y', 0.9),
+			('q', 0.9),
+			('ó', 0.8),
+			('h', 0.7),
+			('f', 0.7),
+			('í', 0.7),
+			('á', 0.5),
+			('z', 0.5),
+			('j', 0.4),
+			('é', 0.4),
+			('ñ', 0.3),
+			('ú', 0.2),
+			('x', 0.2),
+			('ü', 0.02),
+			('k', 0.01),
+			('w', 0.01),
+		],
+		"de es en el la os ue ar ra re er as on st ad al nt co ta ci do an se qu",
+	),
+	(
+		"Dutch",
+		&[
+			('e', 18.9),
+			('n', 10.0),
+			('a', 7.5),
+			('t', 6.8),
+			('i', 6.5),
+			('r', 6.4),
+			('o', 6.1),
+			('d', 5.9),
+			('s', 3.7),
+			('l', 3.6),
+			('g', 3.4),
+			('v', 2.9),
+			('h', 2.4),
+			('k', 2.3),
+			('m', 2.2),
+			('u', 2.0),
+			('b', 1.6),
+			('p', 1.6),
+			('w', 1.5),
+			('j', 1.5),
+			('z', 1.4),
+			('c', 1.2),
+			('f', 0.8),
+			('ë', 0.02),
+			('é', 0.02),
+			('y', 0.04),
+			('x', 0.04),
+			('q', 0.01),
+		],
+		"en de er an te et in ee nd ge ie aa st el ij oo ch ve he re le ng ar ni",
+	),
+	(
+		"Swedish",
+		&[
+			('e', 10.1),
+			('a', 9.4),
+			('n', 8.5),
+			('r', 8.4),
+			('t', 7.7),
+			('s', 6.6),
+			('i', 5.8),
+			('l', 5.3),
+			('d', 4.7),
+			('o', 4.5),
+			('m', 3.5),
+			('k', 3.1),
+			('g', 2.9),
+			('v', 2.4),
+			('h', 2.1),
+			('f', 2.0),
+			('u', 1.9),
+			('p', 1.8),
+			('ä', 1.8),
+			('c', 1.5),
+			('b', 1.5),
+			('ö', 1.3),
+			('å', 1.3),
+			('y', 0.7),
+			('j', 0.6),
+			('x', 0.2),
+			('w', 0.1),
+			('z', 0.1),
+			('q', 0.02),
+		],
+		"er en ar de et an te st ag in tt ll ra nd re on ng li ta me ör är la so",
+	),
+	(
+		"Danish",
+		&[
+			('e', 15.5),
+			('r', 9.0),
+			('n', 7.2),
+			('t', 7.0),
+			('a', 6.0),
+			('i', 6.0),
+			('d', 5.9),
+			('s', 5.8),
+			('l', 5.2),
+			('o', 4.6),
+			('g', 4.1),
+			('k', 3.4),
+			('m', 3.2),
+			('f', 2.4),
+			('v', 2.3),
+			('u', 2.0),
+			('b', 2.0),
+			('p', 1.8),
+			('h', 1.6),
+			('å', 1.2),
+			('æ', 0.9),
+			('ø', 0.9),
+			('j', 0.7),
+			('y', 0.7),
+			('c', 0.6),
+			('w', 0.1),
+			('z', 0.03),
+			('x', 0.03),
+			('q', 0.01),
+		],
+		"er en de et te re nd ge ne in st se le ig an ke ve ar ri ed el il ti me",
+	),
+	(
+		"Russian",
+		&[
+			('о', 11.0),
+			('е', 8.5),
+			('а', 8.0),
+			('и', 7.4),
+			('н', 6.7),
+			('т', 6.3),
+			('с', 5.5),
+			('р', 4.7),
+			('в', 4.5),
+			('л', 4.4),
+			('к', 3.5),
+			('м', 3.2),
+			('д', 3.0),
+			('п', 2.8),
+			('у', 2.6),
+			('я', 2.0),
+			('ы', 1.9),
+			('ь', 1.7),
+			('г', 1.7),
+			('з', 1.6),
+			('б', 1.6),
+			('ч', 1.4),
+			('й', 1.2),
+			('х', 1.0),
+			('ж', 0.9),
+			('ш', 0.7),
+			('ю', 0.6),
+			('ц', 0.5),
+			('щ', 0.4),
+			('э', 0.3),
+			('ф', 0.3),
+			('ъ', 0.04),
+			('ё', 0.04),
+		],
+		"ст но то на ен ов ни ра во ко ро ал пр ре ли ер не ос ан ет ел ит по от",
+	),
+];
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn best(bytes: &[u8], encodings: &[Encoding]) -> Candidate {
+		rank(bytes, encodings).into_iter().next().unwrap()
+	}
+
+	#[test]
+	fn test_rank() {
+		let encodings = [
+			Encoding::Utf8,
+			Encoding::Latin1,
+			Encoding::Utf16Le,
+			Encoding::Utf16Be,
+		];
+
+		assert_eq!(best("kaffé".as_bytes(), &encodings).text, "kaffé");
+		assert_eq!(best(b"caf\xe9", &encodings).text, "café");
+		assert_eq!(
+			best(b"\x01\x52\x00u\x00v\x00r\x00e", &encodings).text,
+			"Œuvre"
+		);
+		assert_eq!(
+			best(b"Na\xefve, but it's fine.", &encodings).encoding,
+			Encoding::Latin1
+		);
+	}
+
+	#[test]
+	fn test_rank_scripts() {
+		let encodings = [
+			Encoding::Utf8,
+			Encoding::SingleByte(CodePage::Windows1252),
+			Encoding::SingleByte(CodePage::Koi8R),
+			Encoding::MultiByte(MultiByte::ShiftJis),
+		];
+
+		let russian = Encoding::SingleByte(CodePage::Koi8R)
+			.encode("Привет, мир")
+			.unwrap();
+		let candidate = best(&russian, &encodings);
+		assert_eq!(candidate.text, "Привет, мир");
+		assert!(candidate.confidence > AMBIGUOUS_CONFIDENCE);
+
+		let japanese = Encoding::MultiByte(MultiByte::ShiftJis)
+			.encode("日本語のテキスト")
+			.unwrap();
+		assert_eq!(best(&japanese, &encodings).text, "日本語のテキスト");
+
+		assert_eq!(best("中文".as_bytes(), &encodings).text, "中文");
+	}
+
+	#[test]
+	fn test_guess() {
+		assert_eq!(guess(b"plain ASCII"), Encoding::Utf8);
+		assert_eq!(guess(b"\xff\xfea\x00"), Encoding::Utf16Le);
+		assert_eq!(
+			guess(b"Le caf\xe9 est tr\xe8s bon"),
+			Encoding::SingleByte(CodePage::Windows1252)
+		);
+		assert_eq!(guess("Grüße aus Köln".as_bytes()), Encoding::Utf8);
+
+		let start = &"Grüße aus Köln".as_bytes()[..3];
+		assert_ne!(guess(start), Encoding::Utf8);
+		assert_eq!(guess_start(start), Encoding::Utf8);
+	}
+
+	#[test]
+	fn test_plausibility() {
+		assert!(plausibility("zürich") > plausibility("zÃ¼rich"));
+		assert!(plausibility("Hello, world") > plausibility("H\u{0}e\u{0}l\u{0}l\u{0}o"));
+		assert!(plausibility("smørrebrød") > plausibility("smÃ¸rrebrÃ¸d"));
+		assert!(plausibility("other") > plausibility("ohter"));
+		assert!(plausibility("простой") > plausibility("прсотой"));
+	}
+}
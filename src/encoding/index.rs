@@ -0,0 +1,159 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+/* A WHATWG index, mapping pointers to code points. The mapping tables live in
+ * `data/`, with the entries of the WHATWG indexes, and are parsed on first
+ * use. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+	code_points: Vec<Option<char>>,
+	pointers: HashMap<char, Vec<usize>>,
+}
+
+impl Index {
+	fn parse(data: &str) -> Self {
+		let entries = entries(data).collect::<Vec<_>>();
+
+		let len = entries
+			.iter()
+			.map(|&(pointer, _)| pointer + 1)
+			.max()
+			.unwrap_or(0);
+		let mut code_points = vec![None; len];
+		let mut pointers = HashMap::<char, Vec<usize>>::new();
+
+		for (pointer, ch) in entries {
+			code_points[pointer] = Some(ch);
+			pointers.entry(ch).or_default().push(pointer);
+		}
+
+		for pointers in pointers.values_mut() {
+			pointers.sort_unstable();
+		}
+
+		Self {
+			code_points,
+			pointers,
+		}
+	}
+
+	pub fn code_point(&self, pointer: usize) -> Option<char> {
+		self.code_points.get(pointer).copied().flatten()
+	}
+
+	/* Every pointer for `ch`, lowest first */
+	pub fn pointers(&self, ch: char) -> &[usize] {
+		self.pointers.get(&ch).map(Vec::as_slice).unwrap_or(&[])
+	}
+
+	pub fn pointer(&self, ch: char) -> Option<usize> {
+		self.pointers(ch).first().copied()
+	}
+}
+
+/* The GB18030 four-byte sequences, which map runs of pointers to runs of
+ * code points instead of listing them one by one */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranges(Vec<(usize, char)>);
+
+impl Ranges {
+	pub fn code_point(&self, pointer: usize) -> Option<char> {
+		if (39420..189000).contains(&pointer) || pointer > 1237575 {
+			return None;
+		}
+
+		if pointer == 7457 {
+			return Some('\u{E7C7}');
+		}
+
+		if pointer >= 189000 {
+			return char::from_u32((0x10000 + pointer - 189000) as u32);
+		}
+
+		let i = self.0.partition_point(|&(start, _)| start <= pointer) - 1;
+		let (start, ch) = self.0[i];
+		char::from_u32(ch as u32 + (pointer - start) as u32)
+	}
+
+	pub fn pointer(&self, ch: char) -> usize {
+		if ch == '\u{E7C7}' {
+			return 7457;
+		}
+
+		if ch as u32 >= 0x10000 {
+			return 189000 + ch as usize - 0x10000;
+		}
+
+		let i = self.0.partition_point(|&(_, start)| start <= ch) - 1;
+		let (pointer, start) = self.0[i];
+		pointer + (ch as usize - start as usize)
+	}
+}
+
+fn entries(data: &str) -> impl Iterator<Item = (usize, char)> {
+	data.lines()
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| {
+			/* The WHATWG files pad the pointer and add the character and its
+			 * name, so they can be dropped in as they are */
+			let mut fields = line.split('\t');
+			let (Some(pointer), Some(code_point)) = (fields.next(), fields.next()) else {
+				panic!("Expected '<pointer>\t<code point>'");
+			};
+			let code_point = u32::from_str_radix(code_point.trim_start_matches("0x"), 16).unwrap();
+			(
+				pointer.trim().parse().unwrap(),
+				char::from_u32(code_point).unwrap(),
+			)
+		})
+}
+
+pub fn jis0208() -> &'static Index {
+	static INDEX: OnceLock<Index> = OnceLock::new();
+	INDEX.get_or_init(|| Index::parse(include_str!("../../data/index-jis0208.txt")))
+}
+
+pub fn jis0212() -> &'static Index {
+	static INDEX: OnceLock<Index> = OnceLock::new();
+	INDEX.get_or_init(|| Index::parse(include_str!("../../data/index-jis0212.txt")))
+}
+
+pub fn euc_kr() -> &'static Index {
+	static INDEX: OnceLock<Index> = OnceLock::new();
+	INDEX.get_or_init(|| Index::parse(include_str!("../../data/index-euc-kr.txt")))
+}
+
+pub fn gb18030() -> &'static Index {
+	static INDEX: OnceLock<Index> = OnceLock::new();
+	INDEX.get_or_init(|| Index::parse(include_str!("../../data/index-gb18030.txt")))
+}
+
+pub fn big5() -> &'static Index {
+	static INDEX: OnceLock<Index> = OnceLock::new();
+	INDEX.get_or_init(|| Index::parse(include_str!("../../data/index-big5.txt")))
+}
+
+pub fn gb18030_ranges() -> &'static Ranges {
+	static RANGES: OnceLock<Ranges> = OnceLock::new();
+	RANGES.get_or_init(|| {
+		Ranges(entries(include_str!("../../data/index-gb18030-ranges.txt")).collect())
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_whatwg_format() {
+		let index = Index::parse("# Comment\n\n  942\t0x43F0\t䏰 (<CJK Ideograph Extension A>)\n");
+		assert_eq!(index.code_point(942), Some('䏰'));
+		assert_eq!(index.pointer('䏰'), Some(942));
+	}
+
+	#[test]
+	fn test_whatwg_entries() {
+		assert_eq!(jis0212().code_point(116), Some('～'));
+		assert_eq!(gb18030().code_point(7186), Some('\u{FE14}'));
+		assert_eq!(big5().code_point(5120), Some('\u{AF}'));
+	}
+}
@@ -0,0 +1,258 @@
+use super::{CodePage, Encoding, MultiByte};
+
+/* Labels from the WHATWG encoding standard, plus common IANA aliases. Unlike
+ * in WHATWG, the ISO-8859 labels name the ISO tables rather than the Windows
+ * code pages that extend them, so `latin1` really is ISO-8859-1. */
+pub const LABELS: &[(&str, Encoding)] = &[
+	("unicode-1-1-utf-8", Encoding::Utf8),
+	("unicode11utf8", Encoding::Utf8),
+	("unicode20utf8", Encoding::Utf8),
+	("utf-8", Encoding::Utf8),
+	("utf8", Encoding::Utf8),
+	("x-unicode20utf8", Encoding::Utf8),
+	("csunicode", Encoding::Utf16Le),
+	("iso-10646-ucs-2", Encoding::Utf16Le),
+	("ucs-2", Encoding::Utf16Le),
+	("unicode", Encoding::Utf16Le),
+	("unicodefeff", Encoding::Utf16Le),
+	("utf-16", Encoding::Utf16Le),
+	("utf-16le", Encoding::Utf16Le),
+	("unicodefffe", Encoding::Utf16Be),
+	("utf-16be", Encoding::Utf16Be),
+	("utf-32", Encoding::Utf32Le),
+	("utf-32le", Encoding::Utf32Le),
+	("utf-32be", Encoding::Utf32Be),
+	("csutf7", Encoding::Utf7),
+	("unicode-1-1-utf-7", Encoding::Utf7),
+	("utf-7", Encoding::Utf7),
+	("utf7", Encoding::Utf7),
+	("cp819", Encoding::Latin1),
+	("csisolatin1", Encoding::Latin1),
+	("ibm819", Encoding::Latin1),
+	("iso-8859-1", Encoding::Latin1),
+	("iso-ir-100", Encoding::Latin1),
+	("iso8859-1", Encoding::Latin1),
+	("iso88591", Encoding::Latin1),
+	("iso_8859-1", Encoding::Latin1),
+	("iso_8859-1:1987", Encoding::Latin1),
+	("l1", Encoding::Latin1),
+	("latin1", Encoding::Latin1),
+	("cp1250", single_byte(CodePage::Windows1250)),
+	("windows-1250", single_byte(CodePage::Windows1250)),
+	("x-cp1250", single_byte(CodePage::Windows1250)),
+	("cp1251", single_byte(CodePage::Windows1251)),
+	("windows-1251", single_byte(CodePage::Windows1251)),
+	("x-cp1251", single_byte(CodePage::Windows1251)),
+	("ansi_x3.4-1968", single_byte(CodePage::Windows1252)),
+	("ascii", single_byte(CodePage::Windows1252)),
+	("cp1252", single_byte(CodePage::Windows1252)),
+	("us-ascii", single_byte(CodePage::Windows1252)),
+	("windows-1252", single_byte(CodePage::Windows1252)),
+	("x-cp1252", single_byte(CodePage::Windows1252)),
+	("cp1253", single_byte(CodePage::Windows1253)),
+	("windows-1253", single_byte(CodePage::Windows1253)),
+	("x-cp1253", single_byte(CodePage::Windows1253)),
+	("cp1254", single_byte(CodePage::Windows1254)),
+	("windows-1254", single_byte(CodePage::Windows1254)),
+	("x-cp1254", single_byte(CodePage::Windows1254)),
+	("cp1255", single_byte(CodePage::Windows1255)),
+	("windows-1255", single_byte(CodePage::Windows1255)),
+	("x-cp1255", single_byte(CodePage::Windows1255)),
+	("cp1256", single_byte(CodePage::Windows1256)),
+	("windows-1256", single_byte(CodePage::Windows1256)),
+	("x-cp1256", single_byte(CodePage::Windows1256)),
+	("cp1257", single_byte(CodePage::Windows1257)),
+	("windows-1257", single_byte(CodePage::Windows1257)),
+	("x-cp1257", single_byte(CodePage::Windows1257)),
+	("cp1258", single_byte(CodePage::Windows1258)),
+	("windows-1258", single_byte(CodePage::Windows1258)),
+	("x-cp1258", single_byte(CodePage::Windows1258)),
+	("csisolatin2", single_byte(CodePage::Iso8859_2)),
+	("iso-8859-2", single_byte(CodePage::Iso8859_2)),
+	("iso-ir-101", single_byte(CodePage::Iso8859_2)),
+	("iso8859-2", single_byte(CodePage::Iso8859_2)),
+	("iso88592", single_byte(CodePage::Iso8859_2)),
+	("iso_8859-2", single_byte(CodePage::Iso8859_2)),
+	("iso_8859-2:1987", single_byte(CodePage::Iso8859_2)),
+	("l2", single_byte(CodePage::Iso8859_2)),
+	("latin2", single_byte(CodePage::Iso8859_2)),
+	("csisolatin3", single_byte(CodePage::Iso8859_3)),
+	("iso-8859-3", single_byte(CodePage::Iso8859_3)),
+	("iso-ir-109", single_byte(CodePage::Iso8859_3)),
+	("iso8859-3", single_byte(CodePage::Iso8859_3)),
+	("iso88593", single_byte(CodePage::Iso8859_3)),
+	("iso_8859-3", single_byte(CodePage::Iso8859_3)),
+	("iso_8859-3:1988", single_byte(CodePage::Iso8859_3)),
+	("l3", single_byte(CodePage::Iso8859_3)),
+	("latin3", single_byte(CodePage::Iso8859_3)),
+	("csisolatin4", single_byte(CodePage::Iso8859_4)),
+	("iso-8859-4", single_byte(CodePage::Iso8859_4)),
+	("iso-ir-110", single_byte(CodePage::Iso8859_4)),
+	("iso8859-4", single_byte(CodePage::Iso8859_4)),
+	("iso88594", single_byte(CodePage::Iso8859_4)),
+	("iso_8859-4", single_byte(CodePage::Iso8859_4)),
+	("iso_8859-4:1988", single_byte(CodePage::Iso8859_4)),
+	("l4", single_byte(CodePage::Iso8859_4)),
+	("latin4", single_byte(CodePage::Iso8859_4)),
+	("csisolatincyrillic", single_byte(CodePage::Iso8859_5)),
+	("cyrillic", single_byte(CodePage::Iso8859_5)),
+	("iso-8859-5", single_byte(CodePage::Iso8859_5)),
+	("iso-ir-144", single_byte(CodePage::Iso8859_5)),
+	("iso8859-5", single_byte(CodePage::Iso8859_5)),
+	("iso88595", single_byte(CodePage::Iso8859_5)),
+	("iso_8859-5", single_byte(CodePage::Iso8859_5)),
+	("iso_8859-5:1988", single_byte(CodePage::Iso8859_5)),
+	("arabic", single_byte(CodePage::Iso8859_6)),
+	("asmo-708", single_byte(CodePage::Iso8859_6)),
+	("csiso88596e", single_byte(CodePage::Iso8859_6)),
+	("csiso88596i", single_byte(CodePage::Iso8859_6)),
+	("csisolatinarabic", single_byte(CodePage::Iso8859_6)),
+	("ecma-114", single_byte(CodePage::Iso8859_6)),
+	("iso-8859-6", single_byte(CodePage::Iso8859_6)),
+	("iso-8859-6-e", single_byte(CodePage::Iso8859_6)),
+	("iso-8859-6-i", single_byte(CodePage::Iso8859_6)),
+	("iso-ir-127", single_byte(CodePage::Iso8859_6)),
+	("iso8859-6", single_byte(CodePage::Iso8859_6)),
+	("iso88596", single_byte(CodePage::Iso8859_6)),
+	("iso_8859-6", single_byte(CodePage::Iso8859_6)),
+	("iso_8859-6:1987", single_byte(CodePage::Iso8859_6)),
+	("csisolatingreek", single_byte(CodePage::Iso8859_7)),
+	("ecma-118", single_byte(CodePage::Iso8859_7)),
+	("elot_928", single_byte(CodePage::Iso8859_7)),
+	("greek", single_byte(CodePage::Iso8859_7)),
+	("greek8", single_byte(CodePage::Iso8859_7)),
+	("iso-8859-7", single_byte(CodePage::Iso8859_7)),
+	("iso-ir-126", single_byte(CodePage::Iso8859_7)),
+	("iso8859-7", single_byte(CodePage::Iso8859_7)),
+	("iso88597", single_byte(CodePage::Iso8859_7)),
+	("iso_8859-7", single_byte(CodePage::Iso8859_7)),
+	("iso_8859-7:1987", single_byte(CodePage::Iso8859_7)),
+	("sun_eu_greek", single_byte(CodePage::Iso8859_7)),
+	("csiso88598e", single_byte(CodePage::Iso8859_8)),
+	("csiso88598i", single_byte(CodePage::Iso8859_8)),
+	("csisolatinhebrew", single_byte(CodePage::Iso8859_8)),
+	("hebrew", single_byte(CodePage::Iso8859_8)),
+	("iso-8859-8", single_byte(CodePage::Iso8859_8)),
+	("iso-8859-8-e", single_byte(CodePage::Iso8859_8)),
+	("iso-8859-8-i", single_byte(CodePage::Iso8859_8)),
+	("iso-ir-138", single_byte(CodePage::Iso8859_8)),
+	("iso8859-8", single_byte(CodePage::Iso8859_8)),
+	("iso88598", single_byte(CodePage::Iso8859_8)),
+	("iso_8859-8", single_byte(CodePage::Iso8859_8)),
+	("iso_8859-8:1988", single_byte(CodePage::Iso8859_8)),
+	("logical", single_byte(CodePage::Iso8859_8)),
+	("visual", single_byte(CodePage::Iso8859_8)),
+	("csisolatin5", single_byte(CodePage::Iso8859_9)),
+	("iso-8859-9", single_byte(CodePage::Iso8859_9)),
+	("iso-ir-148", single_byte(CodePage::Iso8859_9)),
+	("iso8859-9", single_byte(CodePage::Iso8859_9)),
+	("iso88599", single_byte(CodePage::Iso8859_9)),
+	("iso_8859-9", single_byte(CodePage::Iso8859_9)),
+	("iso_8859-9:1989", single_byte(CodePage::Iso8859_9)),
+	("l5", single_byte(CodePage::Iso8859_9)),
+	("latin5", single_byte(CodePage::Iso8859_9)),
+	("csisolatin6", single_byte(CodePage::Iso8859_10)),
+	("iso-8859-10", single_byte(CodePage::Iso8859_10)),
+	("iso-ir-157", single_byte(CodePage::Iso8859_10)),
+	("iso8859-10", single_byte(CodePage::Iso8859_10)),
+	("iso885910", single_byte(CodePage::Iso8859_10)),
+	("l6", single_byte(CodePage::Iso8859_10)),
+	("latin6", single_byte(CodePage::Iso8859_10)),
+	("iso-8859-11", single_byte(CodePage::Iso8859_11)),
+	("iso8859-11", single_byte(CodePage::Iso8859_11)),
+	("iso885911", single_byte(CodePage::Iso8859_11)),
+	("tis-620", single_byte(CodePage::Iso8859_11)),
+	("iso-8859-13", single_byte(CodePage::Iso8859_13)),
+	("iso8859-13", single_byte(CodePage::Iso8859_13)),
+	("iso885913", single_byte(CodePage::Iso8859_13)),
+	("l7", single_byte(CodePage::Iso8859_13)),
+	("latin7", single_byte(CodePage::Iso8859_13)),
+	("iso-8859-14", single_byte(CodePage::Iso8859_14)),
+	("iso8859-14", single_byte(CodePage::Iso8859_14)),
+	("iso885914", single_byte(CodePage::Iso8859_14)),
+	("l8", single_byte(CodePage::Iso8859_14)),
+	("latin8", single_byte(CodePage::Iso8859_14)),
+	("csisolatin9", single_byte(CodePage::Iso8859_15)),
+	("iso-8859-15", single_byte(CodePage::Iso8859_15)),
+	("iso8859-15", single_byte(CodePage::Iso8859_15)),
+	("iso885915", single_byte(CodePage::Iso8859_15)),
+	("iso_8859-15", single_byte(CodePage::Iso8859_15)),
+	("l9", single_byte(CodePage::Iso8859_15)),
+	("latin9", single_byte(CodePage::Iso8859_15)),
+	("iso-8859-16", single_byte(CodePage::Iso8859_16)),
+	("iso8859-16", single_byte(CodePage::Iso8859_16)),
+	("iso885916", single_byte(CodePage::Iso8859_16)),
+	("l10", single_byte(CodePage::Iso8859_16)),
+	("latin10", single_byte(CodePage::Iso8859_16)),
+	("cskoi8r", single_byte(CodePage::Koi8R)),
+	("koi", single_byte(CodePage::Koi8R)),
+	("koi8", single_byte(CodePage::Koi8R)),
+	("koi8-r", single_byte(CodePage::Koi8R)),
+	("koi8_r", single_byte(CodePage::Koi8R)),
+	("koi8-ru", single_byte(CodePage::Koi8U)),
+	("koi8-u", single_byte(CodePage::Koi8U)),
+	("csmacintosh", single_byte(CodePage::MacRoman)),
+	("mac", single_byte(CodePage::MacRoman)),
+	("macintosh", single_byte(CodePage::MacRoman)),
+	("x-mac-roman", single_byte(CodePage::MacRoman)),
+	("437", single_byte(CodePage::Ibm437)),
+	("cp437", single_byte(CodePage::Ibm437)),
+	("cspc8codepage437", single_byte(CodePage::Ibm437)),
+	("ibm437", single_byte(CodePage::Ibm437)),
+	("csshiftjis", multi_byte(MultiByte::ShiftJis)),
+	("ms932", multi_byte(MultiByte::ShiftJis)),
+	("ms_kanji", multi_byte(MultiByte::ShiftJis)),
+	("shift-jis", multi_byte(MultiByte::ShiftJis)),
+	("shift_jis", multi_byte(MultiByte::ShiftJis)),
+	("sjis", multi_byte(MultiByte::ShiftJis)),
+	("windows-31j", multi_byte(MultiByte::ShiftJis)),
+	("x-sjis", multi_byte(MultiByte::ShiftJis)),
+	("cseucpkdfmtjapanese", multi_byte(MultiByte::EucJp)),
+	("euc-jp", multi_byte(MultiByte::EucJp)),
+	("x-euc-jp", multi_byte(MultiByte::EucJp)),
+	("csiso2022jp", multi_byte(MultiByte::Iso2022Jp)),
+	("iso-2022-jp", multi_byte(MultiByte::Iso2022Jp)),
+	("chinese", multi_byte(MultiByte::Gbk)),
+	("csgb2312", multi_byte(MultiByte::Gbk)),
+	("csiso58gb231280", multi_byte(MultiByte::Gbk)),
+	("gb2312", multi_byte(MultiByte::Gbk)),
+	("gb_2312", multi_byte(MultiByte::Gbk)),
+	("gb_2312-80", multi_byte(MultiByte::Gbk)),
+	("gbk", multi_byte(MultiByte::Gbk)),
+	("iso-ir-58", multi_byte(MultiByte::Gbk)),
+	("x-gbk", multi_byte(MultiByte::Gbk)),
+	("gb18030", multi_byte(MultiByte::Gb18030)),
+	("big5", multi_byte(MultiByte::Big5)),
+	("big5-hkscs", multi_byte(MultiByte::Big5)),
+	("cn-big5", multi_byte(MultiByte::Big5)),
+	("csbig5", multi_byte(MultiByte::Big5)),
+	("x-x-big5", multi_byte(MultiByte::Big5)),
+	("cseuckr", multi_byte(MultiByte::EucKr)),
+	("csksc56011987", multi_byte(MultiByte::EucKr)),
+	("euc-kr", multi_byte(MultiByte::EucKr)),
+	("iso-ir-149", multi_byte(MultiByte::EucKr)),
+	("korean", multi_byte(MultiByte::EucKr)),
+	("ks_c_5601-1987", multi_byte(MultiByte::EucKr)),
+	("ks_c_5601-1989", multi_byte(MultiByte::EucKr)),
+	("ksc5601", multi_byte(MultiByte::EucKr)),
+	("ksc_5601", multi_byte(MultiByte::EucKr)),
+	("windows-949", multi_byte(MultiByte::EucKr)),
+];
+
+const fn single_byte(code_page: CodePage) -> Encoding {
+	Encoding::SingleByte(code_page)
+}
+
+const fn multi_byte(multi_byte: MultiByte) -> Encoding {
+	Encoding::MultiByte(multi_byte)
+}
+
+/* Labels are matched case-insensitively, ignoring surrounding whitespace */
+pub fn lookup(label: &str) -> Option<Encoding> {
+	let label = label.trim_matches(|ch: char| ch.is_ascii_whitespace());
+
+	LABELS
+		.iter()
+		.find(|(candidate, _)| candidate.eq_ignore_ascii_case(label))
+		.map(|&(_, encoding)| encoding)
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use super::{CodePage, EncodeError, Encoding, detect::plausibility};
+use crate::utf8::{self, Utf8Error};
+
+/* The encodings UTF-8 text is most often mistaken for */
+pub const MISREADINGS: [Encoding; 3] = [
+	Encoding::Latin1,
+	Encoding::SingleByte(CodePage::Windows1252),
+	Encoding::SingleByte(CodePage::MacRoman),
+];
+
+/* Text can't realistically have been mangled more often than this */
+pub const MAX_LAYERS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+	pub text: String,
+	/* The encodings the UTF-8 bytes were read as, the outermost layer first */
+	pub layers: Vec<Encoding>,
+}
+
+impl Repair {
+	pub fn is_repaired(&self) -> bool {
+		!self.layers.is_empty()
+	}
+}
+
+/* Undoes the layers of UTF-8 that was read as one of `MISREADINGS` that make
+ * the text most plausible. Every order of up to `MAX_LAYERS` misreadings is
+ * tried, as a layer can look worse until the one below it is undone too. A
+ * layer can only be undone if the text encodes to valid UTF-8, so correct text
+ * is left alone. */
+pub fn repair(text: &str) -> Repair {
+	let mut best = Repair {
+		text: text.to_string(),
+		layers: Vec::new(),
+	};
+	let mut best_score = plausibility(text);
+	let mut seen = HashSet::from([best.text.clone()]);
+	let mut chains = vec![best.clone()];
+
+	/* One layer more each round, so a shorter repair wins a tie. Within a round
+	 * the first misreading wins, as the more common one. */
+	for _ in 0..MAX_LAYERS {
+		chains = chains
+			.iter()
+			/* Only text with non-ASCII characters has a layer to undo */
+			.filter(|chain| !chain.text.is_ascii())
+			.flat_map(|chain| {
+				MISREADINGS.into_iter().filter_map(|encoding| {
+					let text = undo(&chain.text, encoding).ok()?;
+					let layers = [chain.layers.as_slice(), &[encoding]].concat();
+					Some(Repair { text, layers })
+				})
+			})
+			.filter(|chain| seen.insert(chain.text.clone()))
+			.collect();
+
+		for chain in &chains {
+			let score = plausibility(&chain.text);
+			if score > best_score {
+				best_score = score;
+				best = chain.clone();
+			}
+		}
+	}
+
+	best
+}
+
+/* Reverses one layer of UTF-8 read as `encoding`. Fails on characters the
+ * encoding has no byte for, and on bytes that aren't UTF-8. */
+pub fn undo(text: &str, encoding: Encoding) -> Result<String, MojibakeError> {
+	let bytes = encoding.encode(text)?;
+	String::from_utf8(bytes).map_err(|err| {
+		let error = utf8::errors(err.as_bytes()).next();
+		error.expect("Invalid UTF-8 has an error").into()
+	})
+}
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MojibakeError {
+	#[error(transparent)]
+	Unmappable(#[from] EncodeError),
+	#[error(transparent)]
+	Utf8(#[from] Utf8Error),
+}
+
+/* Reverses one layer of UTF-8 that was read as Windows-1252, or as Latin-1 when
+ * it has C1 controls where Windows-1252 has € and curly quotes */
+pub fn unmangle(text: &str) -> Result<String, MojibakeError> {
+	undo(text, Encoding::SingleByte(CodePage::Windows1252))
+		.or_else(|err| undo(text, Encoding::Latin1).map_err(|_| err))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn mangle(text: &str, encoding: Encoding) -> String {
+		encoding.decode(text.as_bytes()).unwrap()
+	}
+
+	#[test]
+	fn test_repair() {
+		let repair = repair("religiÃ«n");
+		assert_eq!(repair.text, "religiën");
+		assert_eq!(repair.layers, [Encoding::Latin1]);
+
+		let smart = mangle("“smart” quotes", MISREADINGS[1]);
+		assert_eq!(smart, "â€œsmartâ€\u{9d} quotes");
+		assert_eq!(super::repair(&smart).text, "“smart” quotes");
+
+		let mac = mangle("Crème brûlée", MISREADINGS[2]);
+		assert_eq!(super::repair(&mac).text, "Crème brûlée");
+		assert_eq!(super::repair(&mac).layers, [MISREADINGS[2]]);
+	}
+
+	#[test]
+	fn test_repair_layers() {
+		let twice = mangle(&mangle("pugilarão", Encoding::Latin1), Encoding::Latin1);
+		let repair = repair(&twice);
+		assert_eq!(repair.text, "pugilarão");
+		assert_eq!(repair.layers.len(), 2);
+
+		let mixed = mangle(&mangle("smørrebrød", MISREADINGS[0]), MISREADINGS[2]);
+		let repair = super::repair(&mixed);
+		assert_eq!(repair.text, "smørrebrød");
+		assert_eq!(repair.layers, [MISREADINGS[2], MISREADINGS[0]]);
+
+		/* Undoing only the outer layer exposes a C1 control, which looks worse
+		 * than what it started with */
+		let quoted = mangle(&mangle("“smørrebrød”", MISREADINGS[1]), MISREADINGS[2]);
+		let repair = super::repair(&quoted);
+		assert_eq!(repair.text, "“smørrebrød”");
+		assert_eq!(repair.layers, [MISREADINGS[2], MISREADINGS[1]]);
+	}
+
+	#[test]
+	fn test_undo() {
+		assert_eq!(undo("cafÃ©", Encoding::Latin1).as_deref(), Ok("café"));
+		assert_eq!(undo("plain", Encoding::Latin1).as_deref(), Ok("plain"));
+		assert!(matches!(
+			undo("â€œ", Encoding::Latin1),
+			Err(MojibakeError::Unmappable(EncodeError { ch: '€', .. }))
+		));
+	}
+
+	#[test]
+	fn test_unmangle() {
+		assert_eq!(unmangle("â€œ50 â‚¬â€\u{9d}").as_deref(), Ok("“50 €”"));
+		assert_eq!(unmangle("Å“uvre â„¢").as_deref(), Ok("œuvre ™"));
+		assert_eq!(unmangle("pugilarÃ\u{83}Â£o").as_deref(), Ok("pugilarÃ£o"));
+
+		assert_eq!(
+			unmangle("Ã© ≠"),
+			Err(MojibakeError::Unmappable(EncodeError {
+				offset: 5,
+				ch: '≠',
+				encoding: Encoding::SingleByte(CodePage::Windows1252),
+			}))
+		);
+		assert!(matches!(
+			unmangle("cafÃ"),
+			Err(MojibakeError::Utf8(Utf8Error { offset: 3, .. }))
+		));
+	}
+
+	#[test]
+	fn test_repair_leaves_text_alone() {
+		for text in ["plain", "naïve café", "Привет", "日本語", "Ã is a letter"] {
+			let repair = repair(text);
+			assert_eq!(repair.text, text);
+			assert!(!repair.is_repaired());
+		}
+	}
+}
@@ -0,0 +1,342 @@
+mod chinese;
+mod japanese;
+mod korean;
+
+use std::fmt::Display;
+
+use super::{DecodeError, Encoding};
+
+/* Legacy East Asian encodings, decoded and encoded as described in the WHATWG
+ * encoding standard */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MultiByte {
+	ShiftJis,
+	EucJp,
+	Iso2022Jp,
+	Gbk,
+	Gb18030,
+	Big5,
+	EucKr,
+}
+
+impl MultiByte {
+	pub const ALL: [MultiByte; 7] = [
+		MultiByte::ShiftJis,
+		MultiByte::EucJp,
+		MultiByte::Iso2022Jp,
+		MultiByte::Gbk,
+		MultiByte::Gb18030,
+		MultiByte::Big5,
+		MultiByte::EucKr,
+	];
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			MultiByte::ShiftJis => "Shift_JIS",
+			MultiByte::EucJp => "EUC-JP",
+			MultiByte::Iso2022Jp => "ISO-2022-JP",
+			MultiByte::Gbk => "GBK",
+			MultiByte::Gb18030 => "gb18030",
+			MultiByte::Big5 => "Big5",
+			MultiByte::EucKr => "EUC-KR",
+		}
+	}
+}
+
+impl Display for MultiByte {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}
+}
+
+/* What a decoder does with one byte, or with the end of the input */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+	Finished,
+	Continue,
+	Char(char),
+	Chars(char, char),
+	Error,
+}
+
+/* Bytes a decoder has to look at again, because they turned out not to belong
+ * to the sequence it was decoding. The last one is read first. */
+type Prepend = Vec<u8>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+	ShiftJis(japanese::ShiftJis),
+	EucJp(japanese::EucJp),
+	Iso2022Jp(japanese::Iso2022Jp),
+	Gb18030(chinese::Gb18030),
+	Big5(chinese::Big5),
+	EucKr(korean::EucKr),
+}
+
+impl State {
+	fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		match self {
+			State::ShiftJis(state) => state.handle(byte, prepend),
+			State::EucJp(state) => state.handle(byte, prepend),
+			State::Iso2022Jp(state) => state.handle(byte, prepend),
+			State::Gb18030(state) => state.handle(byte, prepend),
+			State::Big5(state) => state.handle(byte, prepend),
+			State::EucKr(state) => state.handle(byte, prepend),
+		}
+	}
+}
+
+/* A stateful decoder that can be fed its input in chunks. Sequences that are
+ * split over chunks are kept until the rest arrives. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoder {
+	encoding: MultiByte,
+	state: State,
+	prepend: Prepend,
+	/* How many bytes were read so far, to report errors with */
+	offset: usize,
+}
+
+impl Decoder {
+	pub fn new(encoding: MultiByte) -> Self {
+		let state = match encoding {
+			MultiByte::ShiftJis => State::ShiftJis(Default::default()),
+			MultiByte::EucJp => State::EucJp(Default::default()),
+			MultiByte::Iso2022Jp => State::Iso2022Jp(Default::default()),
+			MultiByte::Gbk | MultiByte::Gb18030 => State::Gb18030(Default::default()),
+			MultiByte::Big5 => State::Big5(Default::default()),
+			MultiByte::EucKr => State::EucKr(Default::default()),
+		};
+
+		Self {
+			encoding,
+			state,
+			prepend: Vec::new(),
+			offset: 0,
+		}
+	}
+
+	/* Decodes `bytes` into `out`. Pass `last` with the final chunk, so an
+	 * incomplete sequence at the end is reported. Errors point at the byte
+	 * where the sequence turned out to be malformed. */
+	pub fn decode(
+		&mut self,
+		bytes: &[u8],
+		last: bool,
+		out: &mut String,
+	) -> Result<(), DecodeError> {
+		let encoding = Encoding::MultiByte(self.encoding);
+		self.run(bytes, last, out, |offset, _| {
+			Err(DecodeError::Malformed { offset, encoding })
+		})
+	}
+
+	/* Like `decode`, but replaces malformed sequences by U+FFFD. Returns how
+	 * many were replaced. */
+	pub fn decode_lossy(&mut self, bytes: &[u8], last: bool, out: &mut String) -> usize {
+		let mut errors = 0;
+		let result = self.run(bytes, last, out, |_, out| {
+			out.push(crate::utf8::REPLACEMENT_CHARACTER);
+			errors += 1;
+			Ok(())
+		});
+		debug_assert!(result.is_ok());
+
+		errors
+	}
+
+	fn run(
+		&mut self,
+		bytes: &[u8],
+		last: bool,
+		out: &mut String,
+		mut on_error: impl FnMut(usize, &mut String) -> Result<(), DecodeError>,
+	) -> Result<(), DecodeError> {
+		let mut bytes = bytes.iter();
+
+		loop {
+			let byte = match self.prepend.pop() {
+				Some(byte) => Some(byte),
+				None => match bytes.next() {
+					Some(&byte) => {
+						self.offset += 1;
+						Some(byte)
+					}
+					None if last => None,
+					None => return Ok(()),
+				},
+			};
+
+			let offset = match byte {
+				Some(_) => self.offset - self.prepend.len() - 1,
+				None => self.offset,
+			};
+
+			match self.state.handle(byte, &mut self.prepend) {
+				Step::Finished => return Ok(()),
+				Step::Continue => {}
+				Step::Char(ch) => out.push(ch),
+				Step::Chars(first, second) => {
+					out.push(first);
+					out.push(second);
+				}
+				Step::Error => on_error(offset, out)?,
+			}
+		}
+	}
+}
+
+/* A stateful encoder: ISO-2022-JP has to switch between character sets */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Encoder {
+	encoding: MultiByte,
+	iso_2022_jp: japanese::Iso2022JpEncoder,
+}
+
+impl Encoder {
+	pub fn new(encoding: MultiByte) -> Self {
+		Self {
+			encoding,
+			iso_2022_jp: Default::default(),
+		}
+	}
+
+	/* Returns false, without writing anything, if `ch` can't be encoded */
+	pub fn encode_char(&mut self, ch: char, out: &mut Vec<u8>) -> bool {
+		if ch.is_ascii() && self.encoding != MultiByte::Iso2022Jp {
+			out.push(ch as u8);
+			return true;
+		}
+
+		match self.encoding {
+			MultiByte::ShiftJis => japanese::encode_shift_jis(ch, out),
+			MultiByte::EucJp => japanese::encode_euc_jp(ch, out),
+			MultiByte::Iso2022Jp => self.iso_2022_jp.encode(ch, out),
+			MultiByte::Gbk => chinese::encode_gb18030(ch, true, out),
+			MultiByte::Gb18030 => chinese::encode_gb18030(ch, false, out),
+			MultiByte::Big5 => chinese::encode_big5(ch, out),
+			MultiByte::EucKr => korean::encode_euc_kr(ch, out),
+		}
+	}
+
+	/* Writes whatever is needed to end the output in its initial state */
+	pub fn finish(&mut self, out: &mut Vec<u8>) {
+		if self.encoding == MultiByte::Iso2022Jp {
+			self.iso_2022_jp.finish(out);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn decode(encoding: MultiByte, bytes: &[u8]) -> Result<String, DecodeError> {
+		let mut out = String::new();
+		Decoder::new(encoding).decode(bytes, true, &mut out)?;
+		Ok(out)
+	}
+
+	fn encode(encoding: MultiByte, text: &str) -> Vec<u8> {
+		let mut encoder = Encoder::new(encoding);
+		let mut out = Vec::new();
+		for ch in text.chars() {
+			assert!(encoder.encode_char(ch, &mut out), "{encoding}: {ch}");
+		}
+		encoder.finish(&mut out);
+		out
+	}
+
+	#[test]
+	fn test_decode() {
+		let cases: [(MultiByte, &[u8], &str); 8] = [
+			(MultiByte::ShiftJis, b"\x93\xfa\x96\x7b\xb1", "日本ｱ"),
+			(MultiByte::EucJp, b"\xc6\xfc\xcb\xdc\x8e\xb1", "日本ｱ"),
+			(MultiByte::EucJp, b"\x8f\xb0\xa1", "丂"),
+			(MultiByte::Iso2022Jp, b"a\x1b$BF|K\\\x1b(Bb", "a日本b"),
+			(MultiByte::Gbk, b"\xd6\xd0\xce\xc4\x80", "中文€"),
+			(
+				MultiByte::Gb18030,
+				b"\x81\x30\x81\x30\x94\x39\xfc\x36",
+				"\u{80}😀",
+			),
+			(
+				MultiByte::Big5,
+				b"\xa4\xa4\xa4\xe5\x88\x62",
+				"中文\u{CA}\u{304}",
+			),
+			(MultiByte::EucKr, b"\xc7\xd1\xb1\xdb", "한글"),
+		];
+
+		for (encoding, bytes, expected) in cases {
+			assert_eq!(
+				decode(encoding, bytes).as_deref(),
+				Ok(expected),
+				"{encoding}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_decode_errors() {
+		let malformed = |offset, encoding| DecodeError::Malformed {
+			offset,
+			encoding: Encoding::MultiByte(encoding),
+		};
+
+		assert_eq!(
+			decode(MultiByte::ShiftJis, b"ab\x93"),
+			Err(malformed(3, MultiByte::ShiftJis))
+		);
+		/* The ASCII byte after a broken lead byte is kept */
+		let mut out = String::new();
+		let errors = Decoder::new(MultiByte::EucKr).decode_lossy(b"\xc7a", true, &mut out);
+		assert_eq!((out.as_str(), errors), ("\u{FFFD}a", 1));
+		assert_eq!(
+			decode(MultiByte::Iso2022Jp, b"\x1b(Ba\x1b$Bx"),
+			Err(malformed(8, MultiByte::Iso2022Jp))
+		);
+	}
+
+	#[test]
+	fn test_decode_chunks() {
+		let bytes = "日本語のテキスト".as_bytes();
+		let sjis = encode(MultiByte::ShiftJis, "日本語のテキスト");
+
+		let mut decoder = Decoder::new(MultiByte::ShiftJis);
+		let mut out = String::new();
+		for chunk in sjis.chunks(3) {
+			decoder.decode(chunk, false, &mut out).unwrap();
+		}
+		decoder.decode(&[], true, &mut out).unwrap();
+
+		assert_eq!(out.as_bytes(), bytes);
+	}
+
+	#[test]
+	fn test_round_trip() {
+		let cases = [
+			(MultiByte::ShiftJis, "カタカナ、ひらがな、漢字 ｶﾀｶﾅ ①"),
+			(MultiByte::EucJp, "カタカナ、ひらがな、漢字 ｶﾀｶﾅ"),
+			(MultiByte::Iso2022Jp, "カタカナ、ひらがな、漢字 and ASCII"),
+			(MultiByte::Gbk, "简体中文，繁體中文€"),
+			(MultiByte::Gb18030, "简体中文 ü 😀 \u{E5E6}"),
+			(MultiByte::Big5, "繁體中文，香港"),
+			(MultiByte::EucKr, "한국어 텍스트, 漢字"),
+		];
+
+		for (encoding, text) in cases {
+			let bytes = encode(encoding, text);
+			assert_eq!(decode(encoding, &bytes).as_deref(), Ok(text), "{encoding}");
+		}
+	}
+
+	#[test]
+	fn test_unencodable() {
+		let mut out = Vec::new();
+		assert!(!Encoder::new(MultiByte::EucKr).encode_char('ｱ', &mut out));
+		assert!(!Encoder::new(MultiByte::Gbk).encode_char('😀', &mut out));
+		assert!(!Encoder::new(MultiByte::Big5).encode_char('😀', &mut out));
+		assert!(out.is_empty());
+	}
+}
@@ -0,0 +1,207 @@
+use super::{Prepend, Step};
+use crate::encoding::index;
+
+/* Decodes both GBK and gb18030, as GBK is a subset */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gb18030 {
+	first: u8,
+	second: u8,
+	third: u8,
+}
+
+impl Gb18030 {
+	pub fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		let Some(b) = byte else {
+			return match std::mem::take(self) {
+				Gb18030 {
+					first: 0,
+					second: 0,
+					third: 0,
+				} => Step::Finished,
+				_ => Step::Error,
+			};
+		};
+
+		if self.third != 0 {
+			let Gb18030 {
+				first,
+				second,
+				third,
+			} = std::mem::take(self);
+
+			if !b.is_ascii_digit() {
+				prepend.extend([b, third, second]);
+				return Step::Error;
+			}
+
+			let pointer = ((first as usize - 0x81) * 10 * 126 * 10)
+				+ ((second as usize - 0x30) * 10 * 126)
+				+ ((third as usize - 0x81) * 10)
+				+ b as usize - 0x30;
+			return index::gb18030_ranges()
+				.code_point(pointer)
+				.map_or(Step::Error, Step::Char);
+		}
+
+		if self.second != 0 {
+			if (0x81..=0xFE).contains(&b) {
+				self.third = b;
+				return Step::Continue;
+			}
+
+			prepend.extend([b, self.second]);
+			*self = Gb18030::default();
+			return Step::Error;
+		}
+
+		if self.first != 0 {
+			if b.is_ascii_digit() {
+				self.second = b;
+				return Step::Continue;
+			}
+
+			let lead = std::mem::take(&mut self.first) as usize;
+			let offset = if b < 0x7F { 0x40 } else { 0x41 };
+
+			if matches!(b, 0x40..=0x7E | 0x80..=0xFE) {
+				let pointer = (lead - 0x81) * 190 + b as usize - offset;
+				if let Some(ch) = index::gb18030().code_point(pointer) {
+					return Step::Char(ch);
+				}
+			}
+
+			if b.is_ascii() {
+				prepend.push(b);
+			}
+			return Step::Error;
+		}
+
+		match b {
+			0x00..=0x7F => Step::Char(b as char),
+			0x80 => Step::Char('€'),
+			0x81..=0xFE => {
+				self.first = b;
+				Step::Continue
+			}
+			_ => Step::Error,
+		}
+	}
+}
+
+/* GBK is gb18030 without the four-byte sequences, with € as a single byte */
+pub fn encode_gb18030(ch: char, gbk: bool, out: &mut Vec<u8>) -> bool {
+	if ch == '\u{E5E5}' {
+		return false;
+	}
+
+	if gbk && ch == '€' {
+		out.push(0x80);
+		return true;
+	}
+
+	if let Some(pointer) = index::gb18030().pointer(ch) {
+		let (lead, trail) = (pointer / 190, pointer % 190);
+		let offset = if trail < 0x3F { 0x40 } else { 0x41 };
+		out.extend([(lead + 0x81) as u8, (trail + offset) as u8]);
+		return true;
+	}
+
+	if gbk {
+		return false;
+	}
+
+	let mut pointer = index::gb18030_ranges().pointer(ch);
+	let first = pointer / (10 * 126 * 10);
+	pointer %= 10 * 126 * 10;
+	let second = pointer / (10 * 126);
+	pointer %= 10 * 126;
+	let third = pointer / 10;
+	let fourth = pointer % 10;
+
+	out.extend([
+		(first + 0x81) as u8,
+		(second + 0x30) as u8,
+		(third + 0x81) as u8,
+		(fourth + 0x30) as u8,
+	]);
+	true
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Big5 {
+	lead: u8,
+}
+
+impl Big5 {
+	pub fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		let Some(b) = byte else {
+			return match std::mem::take(&mut self.lead) {
+				0 => Step::Finished,
+				_ => Step::Error,
+			};
+		};
+
+		if self.lead != 0 {
+			let lead = std::mem::take(&mut self.lead) as usize;
+			let offset = if b < 0x7F { 0x40 } else { 0x62 };
+
+			if matches!(b, 0x40..=0x7E | 0xA1..=0xFE) {
+				let pointer = (lead - 0x81) * 157 + b as usize - offset;
+
+				/* The only pointers that decode to two code points */
+				match pointer {
+					1133 => return Step::Chars('\u{CA}', '\u{304}'),
+					1135 => return Step::Chars('\u{CA}', '\u{30C}'),
+					1164 => return Step::Chars('\u{EA}', '\u{304}'),
+					1166 => return Step::Chars('\u{EA}', '\u{30C}'),
+					_ => {}
+				}
+
+				if let Some(ch) = index::big5().code_point(pointer) {
+					return Step::Char(ch);
+				}
+			}
+
+			if b.is_ascii() {
+				prepend.push(b);
+			}
+			return Step::Error;
+		}
+
+		match b {
+			0x00..=0x7F => Step::Char(b as char),
+			0x81..=0xFE => {
+				self.lead = b;
+				Step::Continue
+			}
+			_ => Step::Error,
+		}
+	}
+}
+
+/* The HKSCS extensions before lead byte 0xA1 are only decoded, not encoded */
+const BIG5_HKSCS_POINTERS: usize = (0xA1 - 0x81) * 157;
+
+pub fn encode_big5(ch: char, out: &mut Vec<u8>) -> bool {
+	let pointers = index::big5().pointers(ch);
+	let mut pointers = pointers
+		.iter()
+		.filter(|&&pointer| pointer >= BIG5_HKSCS_POINTERS);
+
+	/* These appear twice, and the last one is the one in use */
+	let pointer = match ch {
+		'\u{2550}' | '\u{255E}' | '\u{2561}' | '\u{256A}' | '\u{5341}' | '\u{5345}' => {
+			pointers.next_back()
+		}
+		_ => pointers.next(),
+	};
+
+	let Some(&pointer) = pointer else {
+		return false;
+	};
+
+	let (lead, trail) = (pointer / 157, pointer % 157);
+	let offset = if trail < 0x3F { 0x40 } else { 0x62 };
+	out.extend([(lead + 0x81) as u8, (trail + offset) as u8]);
+	true
+}
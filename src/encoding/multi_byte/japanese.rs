@@ -0,0 +1,403 @@
+use super::{Prepend, Step};
+use crate::encoding::index;
+
+/* Shift_JIS pointers that duplicate the IBM extensions further on, which the
+ * encoder skips in favour of those */
+const SHIFT_JIS_DUPLICATES: std::ops::RangeInclusive<usize> = 8272..=8835;
+/* The user-defined area, which maps to the Private Use Area */
+const EUDC: std::ops::RangeInclusive<usize> = 8836..=10715;
+
+/* The full-width katakana that ISO-2022-JP encodes half-width katakana as */
+const KATAKANA: [u16; 63] = [
+	0x3002, 0x300C, 0x300D, 0x3001, 0x30FB, 0x30F2, 0x30A1, 0x30A3, 0x30A5, 0x30A7, 0x30A9, 0x30E3,
+	0x30E5, 0x30E7, 0x30C3, 0x30FC, 0x30A2, 0x30A4, 0x30A6, 0x30A8, 0x30AA, 0x30AB, 0x30AD, 0x30AF,
+	0x30B1, 0x30B3, 0x30B5, 0x30B7, 0x30B9, 0x30BB, 0x30BD, 0x30BF, 0x30C1, 0x30C4, 0x30C6, 0x30C8,
+	0x30CA, 0x30CB, 0x30CC, 0x30CD, 0x30CE, 0x30CF, 0x30D2, 0x30D5, 0x30D8, 0x30DB, 0x30DE, 0x30DF,
+	0x30E0, 0x30E1, 0x30E2, 0x30E4, 0x30E6, 0x30E8, 0x30E9, 0x30EA, 0x30EB, 0x30EC, 0x30ED, 0x30EF,
+	0x30F3, 0x309B, 0x309C,
+];
+
+fn half_width_katakana(byte: u8) -> char {
+	char::from_u32(0xFF61 - 0xA1 + byte as u32).unwrap()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShiftJis {
+	lead: u8,
+}
+
+impl ShiftJis {
+	pub fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		let Some(b) = byte else {
+			return match std::mem::take(&mut self.lead) {
+				0 => Step::Finished,
+				_ => Step::Error,
+			};
+		};
+
+		if self.lead != 0 {
+			let lead = std::mem::take(&mut self.lead) as usize;
+			let offset = if b < 0x7F { 0x40 } else { 0x41 };
+			let lead_offset = if lead < 0xA0 { 0x81 } else { 0xC1 };
+
+			if matches!(b, 0x40..=0x7E | 0x80..=0xFC) {
+				let pointer = (lead - lead_offset) * 188 + b as usize - offset;
+				if EUDC.contains(&pointer) {
+					return Step::Char(char::from_u32((0xE000 - 8836 + pointer) as u32).unwrap());
+				}
+
+				if let Some(ch) = index::jis0208().code_point(pointer) {
+					return Step::Char(ch);
+				}
+			}
+
+			if b.is_ascii() {
+				prepend.push(b);
+			}
+			return Step::Error;
+		}
+
+		match b {
+			0x00..=0x80 => Step::Char(b as char),
+			0xA1..=0xDF => Step::Char(half_width_katakana(b)),
+			0x81..=0x9F | 0xE0..=0xFC => {
+				self.lead = b;
+				Step::Continue
+			}
+			_ => Step::Error,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EucJp {
+	lead: u8,
+	jis0212: bool,
+}
+
+impl EucJp {
+	pub fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		let Some(b) = byte else {
+			return match std::mem::take(&mut self.lead) {
+				0 => Step::Finished,
+				_ => Step::Error,
+			};
+		};
+
+		match (self.lead, b) {
+			(0x8E, 0xA1..=0xDF) => {
+				self.lead = 0;
+				return Step::Char(half_width_katakana(b));
+			}
+			(0x8F, 0xA1..=0xFE) => {
+				self.jis0212 = true;
+				self.lead = b;
+				return Step::Continue;
+			}
+			(0, _) => {}
+			(lead, _) => {
+				self.lead = 0;
+				let jis0212 = std::mem::take(&mut self.jis0212);
+
+				if (0xA1..=0xFE).contains(&lead) && (0xA1..=0xFE).contains(&b) {
+					let pointer = (lead as usize - 0xA1) * 94 + b as usize - 0xA1;
+					let index = if jis0212 {
+						index::jis0212()
+					} else {
+						index::jis0208()
+					};
+
+					if let Some(ch) = index.code_point(pointer) {
+						return Step::Char(ch);
+					}
+				}
+
+				if b.is_ascii() {
+					prepend.push(b);
+				}
+				return Step::Error;
+			}
+		}
+
+		match b {
+			0x00..=0x7F => Step::Char(b as char),
+			0x8E | 0x8F | 0xA1..=0xFE => {
+				self.lead = b;
+				Step::Continue
+			}
+			_ => Step::Error,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Iso2022JpState {
+	#[default]
+	Ascii,
+	Roman,
+	Katakana,
+	LeadByte,
+	TrailByte,
+	EscapeStart,
+	Escape,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Iso2022Jp {
+	state: Iso2022JpState,
+	/* The state to return to after an escape sequence */
+	output_state: Iso2022JpState,
+	lead: u8,
+	/* Whether anything was decoded since the last escape sequence, as two
+	 * escape sequences in a row are an error */
+	output: bool,
+}
+
+impl Iso2022Jp {
+	pub fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		use Iso2022JpState::*;
+
+		const ESCAPE: u8 = 0x1B;
+
+		match (self.state, byte) {
+			(Ascii | Roman | Katakana | LeadByte, Some(ESCAPE)) => {
+				self.state = EscapeStart;
+				Step::Continue
+			}
+			(Ascii | Roman | Katakana | LeadByte, None) => Step::Finished,
+			(Ascii, Some(b @ 0x00..=0x7F)) if b != 0x0E && b != 0x0F => {
+				self.output = false;
+				Step::Char(b as char)
+			}
+			(Roman, Some(0x5C)) => {
+				self.output = false;
+				Step::Char('\u{A5}')
+			}
+			(Roman, Some(0x7E)) => {
+				self.output = false;
+				Step::Char('\u{203E}')
+			}
+			(Roman, Some(b @ 0x00..=0x7F)) if b != 0x0E && b != 0x0F => {
+				self.output = false;
+				Step::Char(b as char)
+			}
+			(Katakana, Some(b @ 0x21..=0x5F)) => {
+				self.output = false;
+				Step::Char(char::from_u32(0xFF61 - 0x21 + b as u32).unwrap())
+			}
+			(LeadByte, Some(b @ 0x21..=0x7E)) => {
+				self.output = false;
+				self.lead = b;
+				self.state = TrailByte;
+				Step::Continue
+			}
+			(Ascii | Roman | Katakana | LeadByte, Some(_)) => {
+				self.output = false;
+				Step::Error
+			}
+			(TrailByte, Some(ESCAPE)) => {
+				self.state = EscapeStart;
+				Step::Error
+			}
+			(TrailByte, Some(b @ 0x21..=0x7E)) => {
+				self.state = LeadByte;
+				let pointer = (self.lead as usize - 0x21) * 94 + b as usize - 0x21;
+				index::jis0208()
+					.code_point(pointer)
+					.map_or(Step::Error, Step::Char)
+			}
+			(TrailByte, _) => {
+				/* The end of the input is read again in the lead byte state */
+				self.state = LeadByte;
+				Step::Error
+			}
+			(EscapeStart, Some(b @ (0x24 | 0x28))) => {
+				self.lead = b;
+				self.state = Escape;
+				Step::Continue
+			}
+			(EscapeStart, b) => {
+				prepend.extend(b);
+				self.output = false;
+				self.state = self.output_state;
+				Step::Error
+			}
+			(Escape, b) => {
+				let lead = std::mem::take(&mut self.lead);
+				let state = match (lead, b) {
+					(0x28, Some(0x42)) => Some(Ascii),
+					(0x28, Some(0x4A)) => Some(Roman),
+					(0x28, Some(0x49)) => Some(Katakana),
+					(0x24, Some(0x40 | 0x42)) => Some(LeadByte),
+					_ => None,
+				};
+
+				match state {
+					Some(state) => {
+						self.state = state;
+						self.output_state = state;
+						if std::mem::replace(&mut self.output, true) {
+							Step::Error
+						} else {
+							Step::Continue
+						}
+					}
+					None => {
+						prepend.extend(b);
+						prepend.push(lead);
+						self.output = false;
+						self.state = self.output_state;
+						Step::Error
+					}
+				}
+			}
+		}
+	}
+}
+
+pub fn encode_shift_jis(ch: char, out: &mut Vec<u8>) -> bool {
+	let ch = match ch {
+		'\u{80}' => {
+			out.push(0x80);
+			return true;
+		}
+		'\u{A5}' => {
+			out.push(0x5C);
+			return true;
+		}
+		'\u{203E}' => {
+			out.push(0x7E);
+			return true;
+		}
+		'\u{FF61}'..='\u{FF9F}' => {
+			out.push((ch as u32 - 0xFF61 + 0xA1) as u8);
+			return true;
+		}
+		'\u{2212}' => '\u{FF0D}',
+		ch => ch,
+	};
+
+	let Some(&pointer) = index::jis0208()
+		.pointers(ch)
+		.iter()
+		.find(|pointer| !SHIFT_JIS_DUPLICATES.contains(pointer))
+	else {
+		return false;
+	};
+
+	let (lead, trail) = (pointer / 188, pointer % 188);
+	let lead_offset = if lead < 0x1F { 0x81 } else { 0xC1 };
+	let offset = if trail < 0x3F { 0x40 } else { 0x41 };
+	out.extend([(lead + lead_offset) as u8, (trail + offset) as u8]);
+	true
+}
+
+pub fn encode_euc_jp(ch: char, out: &mut Vec<u8>) -> bool {
+	let ch = match ch {
+		'\u{A5}' => {
+			out.push(0x5C);
+			return true;
+		}
+		'\u{203E}' => {
+			out.push(0x7E);
+			return true;
+		}
+		'\u{FF61}'..='\u{FF9F}' => {
+			out.extend([0x8E, (ch as u32 - 0xFF61 + 0xA1) as u8]);
+			return true;
+		}
+		'\u{2212}' => '\u{FF0D}',
+		ch => ch,
+	};
+
+	let Some(pointer) = index::jis0208().pointer(ch) else {
+		return false;
+	};
+
+	out.extend([(pointer / 94 + 0xA1) as u8, (pointer % 94 + 0xA1) as u8]);
+	true
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Iso2022JpEncoderState {
+	#[default]
+	Ascii,
+	Roman,
+	Jis0208,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Iso2022JpEncoder {
+	state: Iso2022JpEncoderState,
+}
+
+impl Iso2022JpEncoder {
+	pub fn encode(&mut self, ch: char, out: &mut Vec<u8>) -> bool {
+		use Iso2022JpEncoderState::*;
+
+		if matches!(ch, '\u{0E}' | '\u{0F}' | '\u{1B}') {
+			return false;
+		}
+
+		match (self.state, ch) {
+			(Ascii, ch) if ch.is_ascii() => {
+				out.push(ch as u8);
+				return true;
+			}
+			(Roman, '\u{A5}') => {
+				out.push(0x5C);
+				return true;
+			}
+			(Roman, '\u{203E}') => {
+				out.push(0x7E);
+				return true;
+			}
+			(Roman, ch) if ch.is_ascii() && ch != '\u{5C}' && ch != '\u{7E}' => {
+				out.push(ch as u8);
+				return true;
+			}
+			(_, ch) if ch.is_ascii() => {
+				self.switch(Ascii, out);
+				return self.encode(ch, out);
+			}
+			(_, '\u{A5}' | '\u{203E}') => {
+				self.switch(Roman, out);
+				return self.encode(ch, out);
+			}
+			_ => {}
+		}
+
+		let ch = match ch {
+			'\u{2212}' => '\u{FF0D}',
+			'\u{FF61}'..='\u{FF9F}' => {
+				char::from_u32(KATAKANA[ch as usize - 0xFF61] as u32).unwrap()
+			}
+			ch => ch,
+		};
+
+		let Some(pointer) = index::jis0208().pointer(ch) else {
+			return false;
+		};
+
+		self.switch(Jis0208, out);
+		out.extend([(pointer / 94 + 0x21) as u8, (pointer % 94 + 0x21) as u8]);
+		true
+	}
+
+	pub fn finish(&mut self, out: &mut Vec<u8>) {
+		self.switch(Iso2022JpEncoderState::Ascii, out);
+	}
+
+	fn switch(&mut self, state: Iso2022JpEncoderState, out: &mut Vec<u8>) {
+		if self.state == state {
+			return;
+		}
+
+		self.state = state;
+		out.extend_from_slice(match state {
+			Iso2022JpEncoderState::Ascii => b"\x1b(B",
+			Iso2022JpEncoderState::Roman => b"\x1b(J",
+			Iso2022JpEncoderState::Jis0208 => b"\x1b$B",
+		});
+	}
+}
@@ -0,0 +1,52 @@
+use super::{Prepend, Step};
+use crate::encoding::index;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EucKr {
+	lead: u8,
+}
+
+impl EucKr {
+	pub fn handle(&mut self, byte: Option<u8>, prepend: &mut Prepend) -> Step {
+		let Some(b) = byte else {
+			return match std::mem::take(&mut self.lead) {
+				0 => Step::Finished,
+				_ => Step::Error,
+			};
+		};
+
+		if self.lead != 0 {
+			let lead = std::mem::take(&mut self.lead) as usize;
+
+			if (0x41..=0xFE).contains(&b) {
+				let pointer = (lead - 0x81) * 190 + b as usize - 0x41;
+				if let Some(ch) = index::euc_kr().code_point(pointer) {
+					return Step::Char(ch);
+				}
+			}
+
+			if b.is_ascii() {
+				prepend.push(b);
+			}
+			return Step::Error;
+		}
+
+		match b {
+			0x00..=0x7F => Step::Char(b as char),
+			0x81..=0xFE => {
+				self.lead = b;
+				Step::Continue
+			}
+			_ => Step::Error,
+		}
+	}
+}
+
+pub fn encode_euc_kr(ch: char, out: &mut Vec<u8>) -> bool {
+	let Some(pointer) = index::euc_kr().pointer(ch) else {
+		return false;
+	};
+
+	out.extend([(pointer / 190 + 0x81) as u8, (pointer % 190 + 0x41) as u8]);
+	true
+}
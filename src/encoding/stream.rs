@@ -0,0 +1,338 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use super::{DecodeError, Decoder, Encoding, transcode::UnmappableError, utf7};
+use crate::utf8;
+
+/* How much is read from a `Read` at once */
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/* Decodes input that arrives in chunks of any size. A sequence that's split
+ * over two chunks is kept until the rest of it arrives, so at most a few bytes
+ * are held back. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamDecoder {
+	encoding: Encoding,
+	/* The multi-byte decoders carry their own state */
+	multi_byte: Option<Decoder>,
+	pending: Vec<u8>,
+	/* How many bytes came before `pending`, to report errors with */
+	offset: usize,
+	/* Whether a byte order mark could still follow */
+	at_start: bool,
+}
+
+impl StreamDecoder {
+	pub fn new(encoding: Encoding) -> Self {
+		Self {
+			encoding,
+			multi_byte: match encoding {
+				Encoding::MultiByte(multi_byte) => Some(Decoder::new(multi_byte)),
+				_ => None,
+			},
+			pending: Vec::new(),
+			offset: 0,
+			at_start: true,
+		}
+	}
+
+	pub fn encoding(&self) -> Encoding {
+		self.encoding
+	}
+
+	/* Decodes `chunk` into `out`, like `Encoding::decode` would decode all
+	 * chunks together. Pass `last` with the final chunk, so an incomplete
+	 * sequence at the end is reported. */
+	pub fn decode(
+		&mut self,
+		chunk: &[u8],
+		last: bool,
+		out: &mut String,
+	) -> Result<(), DecodeError> {
+		if let Some(decoder) = &mut self.multi_byte {
+			return decoder.decode(chunk, last, out);
+		}
+
+		self.pending.extend_from_slice(chunk);
+
+		/* UTF-7's byte order mark can only be recognised once decoded */
+		if self.at_start && self.encoding != Encoding::Utf7 {
+			/* Byte order marks are up to 4 bytes long */
+			if self.pending.len() < 4 && !last {
+				return Ok(());
+			}
+
+			let bom_len = self.pending.len() - self.encoding.strip_bom(&self.pending)?.len();
+			self.pending.drain(..bom_len);
+			self.offset += bom_len;
+			self.at_start = false;
+		}
+
+		let mut len = match last {
+			true => self.pending.len(),
+			false => complete_len(self.encoding, &self.pending),
+		};
+
+		/* A UTF-7 base64 run goes on for as long as the input does. Rather than
+		 * holding all of it back, its complete part is decoded, and the rest
+		 * resumes after a '+' of its own. */
+		let split_run = match self.encoding {
+			Encoding::Utf7 if len < self.pending.len() => utf7::split_run(&self.pending, len),
+			_ => None,
+		};
+		if let Some(split) = split_run {
+			len = split;
+		}
+
+		let text = self
+			.encoding
+			.decode_without_bom(&self.pending[..len])
+			.map_err(|err| err.offset_by(self.offset))?;
+
+		match text.strip_prefix('\u{FEFF}') {
+			Some(text) if self.at_start => out.push_str(text),
+			_ => out.push_str(&text),
+		}
+		self.at_start &= text.is_empty();
+
+		match split_run {
+			Some(_) => {
+				self.pending.drain(..len - 1);
+				self.pending[0] = b'+';
+				self.offset += len - 1;
+			}
+			None => {
+				self.pending.drain(..len);
+				self.offset += len;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/* How much of `bytes` can be decoded without knowing what comes next */
+fn complete_len(encoding: Encoding, bytes: &[u8]) -> usize {
+	let len = bytes.len();
+
+	match encoding {
+		Encoding::Utf8 => {
+			let start = (len.saturating_sub(3)..len)
+				.rev()
+				.find(|&i| !utf8::is_continuation_byte(bytes[i]));
+
+			match start.map(|i| (i, utf8::sequence_size(bytes[i]))) {
+				Some((i, Some(size))) if i + size as usize > len => i,
+				_ => len,
+			}
+		}
+		Encoding::Utf16Le | Encoding::Utf16Be => {
+			let even = len & !1;
+			let last_unit = even.checked_sub(2).map(|i| match encoding {
+				Encoding::Utf16Le => u16::from_le_bytes([bytes[i], bytes[i + 1]]),
+				_ => u16::from_be_bytes([bytes[i], bytes[i + 1]]),
+			});
+
+			/* A high surrogate needs the low surrogate that follows it */
+			match last_unit {
+				Some(0xD800..=0xDBFF) => even - 2,
+				_ => even,
+			}
+		}
+		Encoding::Utf32Le | Encoding::Utf32Be => len - len % 4,
+		Encoding::Utf7 => utf7::complete_len(bytes),
+		Encoding::Latin1 | Encoding::SingleByte(_) | Encoding::MultiByte(_) => len,
+	}
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StreamError {
+	#[error(transparent)]
+	Io(#[from] io::Error),
+	#[error(transparent)]
+	Decode(#[from] DecodeError),
+	#[error(transparent)]
+	Encode(#[from] UnmappableError),
+}
+
+/* Reads until `buffer` is full or the input ends, so every chunk but the last
+ * is full. Returns how many bytes were read. */
+pub fn read_chunk(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+	let mut len = 0;
+
+	while len < buffer.len() {
+		match reader.read(&mut buffer[len..]) {
+			Ok(0) => break,
+			Ok(read) => len += read,
+			Err(err) if err.kind() == ErrorKind::Interrupted => {}
+			Err(err) => return Err(err),
+		}
+	}
+
+	Ok(len)
+}
+
+/* Decodes everything `reader` produces and writes it to `writer` as UTF-8, a
+ * chunk at a time. Returns how many bytes were read. */
+pub fn decode_stream(
+	encoding: Encoding,
+	mut reader: impl Read,
+	mut writer: impl Write,
+) -> Result<u64, StreamError> {
+	let mut decoder = StreamDecoder::new(encoding);
+	let mut buffer = vec![0; CHUNK_SIZE];
+	let mut text = String::new();
+	let mut total = 0;
+
+	loop {
+		let len = read_chunk(&mut reader, &mut buffer)?;
+
+		text.clear();
+		decoder.decode(&buffer[..len], len == 0, &mut text)?;
+		writer.write_all(text.as_bytes())?;
+		total += len as u64;
+
+		if len == 0 {
+			writer.flush()?;
+			return Ok(total);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::encoding::{CodePage, MultiByte};
+
+	/* Decodes `bytes` split up in every possible pair of chunks, and one byte
+	 * at a time */
+	fn decode_split(encoding: Encoding, bytes: &[u8]) -> Result<String, DecodeError> {
+		let mut results = Vec::new();
+
+		for split in 0..=bytes.len() {
+			let mut decoder = StreamDecoder::new(encoding);
+			let mut text = String::new();
+			let result = decoder
+				.decode(&bytes[..split], false, &mut text)
+				.and_then(|()| decoder.decode(&bytes[split..], true, &mut text));
+			results.push(result.map(|()| text));
+		}
+
+		let mut decoder = StreamDecoder::new(encoding);
+		let mut text = String::new();
+		let result = bytes
+			.iter()
+			.try_for_each(|&b| decoder.decode(&[b], false, &mut text))
+			.and_then(|()| decoder.decode(&[], true, &mut text));
+		results.push(result.map(|()| text));
+
+		for result in &results[1..] {
+			assert_eq!(
+				result, &results[0],
+				"{encoding} decodes differently when split"
+			);
+		}
+		results.remove(0)
+	}
+
+	#[test]
+	fn test_stream_decoder() {
+		let text = "a€😀ü日本\u{FEFF}z";
+		let encodings = [
+			Encoding::Utf8,
+			Encoding::Utf16Le,
+			Encoding::Utf16Be,
+			Encoding::Utf32Le,
+			Encoding::Utf32Be,
+			Encoding::Utf7,
+			Encoding::MultiByte(MultiByte::Gb18030),
+		];
+
+		for encoding in encodings {
+			let bytes = encoding.encode(text).unwrap();
+			assert_eq!(decode_split(encoding, &bytes).as_deref(), Ok(text));
+
+			let with_bom = [encoding.bom().unwrap_or_default(), &bytes].concat();
+			assert_eq!(
+				decode_split(encoding, &with_bom),
+				encoding.decode(&with_bom)
+			);
+		}
+
+		/* Only the first U+FEFF can be a byte order mark */
+		for encoding in encodings {
+			let bytes = encoding.encode("\u{FEFF}\u{FEFF}A").unwrap();
+			assert_eq!(
+				decode_split(encoding, &bytes),
+				encoding.decode(&bytes),
+				"{encoding}"
+			);
+		}
+		assert_eq!(
+			decode_split(Encoding::Utf8, b"\xef\xbb\xbf\xef\xbb\xbfA").as_deref(),
+			Ok("\u{FEFF}A")
+		);
+
+		let koi8 = Encoding::SingleByte(CodePage::Koi8R);
+		assert_eq!(decode_split(koi8, b"\xf0\xd2\xc9").as_deref(), Ok("При"));
+	}
+
+	#[test]
+	fn test_stream_decoder_utf7_runs() {
+		let text = "日本😀".repeat(1000);
+		let bytes = Encoding::Utf7.encode(&text).unwrap();
+		assert_eq!(bytes.iter().filter(|&&b| b == b'+').count(), 1);
+
+		let mut decoder = StreamDecoder::new(Encoding::Utf7);
+		let mut decoded = String::new();
+		for &b in &bytes {
+			decoder.decode(&[b], false, &mut decoded).unwrap();
+			assert!(decoder.pending.len() <= 1 + 2 * 8);
+		}
+		decoder.decode(&[], true, &mut decoded).unwrap();
+		assert_eq!(decoded, text);
+	}
+
+	#[test]
+	fn test_stream_decoder_errors() {
+		assert_eq!(
+			decode_split(Encoding::Utf8, b"ab\xe2\x82"),
+			Encoding::Utf8.decode(b"ab\xe2\x82")
+		);
+		assert_eq!(
+			decode_split(Encoding::Utf8, b"ab\xe2\x82x"),
+			Encoding::Utf8.decode(b"ab\xe2\x82x")
+		);
+		assert_eq!(
+			decode_split(Encoding::Utf16Le, b"\x3d\xd8a\x00"),
+			Err(DecodeError::UnpairedSurrogate {
+				offset: 0,
+				unit: 0xD83D,
+			})
+		);
+		assert_eq!(
+			decode_split(Encoding::Utf32Be, b"\x00\x00\x00a\x00\x00"),
+			Err(DecodeError::Truncated { offset: 4 })
+		);
+		assert_eq!(
+			decode_split(Encoding::Utf16Le, b"\xff\xfe\x00\x00"),
+			Encoding::Utf16Le.decode(b"\xff\xfe\x00\x00")
+		);
+	}
+
+	#[test]
+	fn test_decode_stream() {
+		let text = "ü€".repeat(CHUNK_SIZE);
+		let bytes = Encoding::Utf16Be.encode(&text).unwrap();
+
+		let mut out = Vec::new();
+		let read = decode_stream(Encoding::Utf16Be, bytes.as_slice(), &mut out).unwrap();
+		assert_eq!(read, bytes.len() as u64);
+		assert_eq!(String::from_utf8(out).unwrap(), text);
+
+		let truncated = &bytes[..bytes.len() - 1];
+		assert!(matches!(
+			decode_stream(Encoding::Utf16Be, truncated, io::sink()),
+			Err(StreamError::Decode(DecodeError::Truncated { offset })) if offset == truncated.len() - 1
+		));
+	}
+}
@@ -0,0 +1,331 @@
+use std::{
+	collections::BTreeMap,
+	fmt::Display,
+	io::{Read, Write},
+	str::FromStr,
+};
+
+use unidecode::unidecode_char;
+
+use super::{
+	DecodeError, Encoding, detect,
+	stream::{self, CHUNK_SIZE, StreamDecoder, StreamError},
+};
+
+/* What to write for characters the target encoding has no bytes for */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unmappable {
+	#[default]
+	Error,
+	Replace,
+	/* Like &#8364; for € */
+	HtmlReference,
+	/* The closest ASCII, like "EUR" for € */
+	Transliterate,
+}
+
+impl Unmappable {
+	pub const ALL: [Unmappable; 4] = [
+		Unmappable::Error,
+		Unmappable::Replace,
+		Unmappable::HtmlReference,
+		Unmappable::Transliterate,
+	];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Unmappable::Error => "error",
+			Unmappable::Replace => "replace",
+			Unmappable::HtmlReference => "html",
+			Unmappable::Transliterate => "transliterate",
+		}
+	}
+
+	fn substitute(self, ch: char) -> Option<String> {
+		match self {
+			Unmappable::Error => None,
+			Unmappable::Replace => Some("?".to_string()),
+			Unmappable::HtmlReference => Some(format!("&#{};", ch as u32)),
+			/* Some characters, like emoji, have no ASCII at all */
+			Unmappable::Transliterate => match unidecode_char(ch) {
+				"" => Some("?".to_string()),
+				ascii => Some(ascii.to_string()),
+			},
+		}
+	}
+}
+
+impl FromStr for Unmappable {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Unmappable::ALL
+			.into_iter()
+			.find(|policy| policy.name() == s)
+			.ok_or_else(|| format!("Unknown policy '{s}'"))
+	}
+}
+
+impl Display for Unmappable {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+	/* Detected from the input when missing */
+	pub from: Option<Encoding>,
+	pub to: Encoding,
+	pub unmappable: Unmappable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+	pub from: Encoding,
+	pub to: Encoding,
+	pub chars: usize,
+	pub unmappable: Unmappable,
+	/* How often each character that couldn't be encoded was seen */
+	pub affected: BTreeMap<char, usize>,
+}
+
+impl Summary {
+	fn new(from: Encoding, options: &Options) -> Self {
+		Summary {
+			from,
+			to: options.to,
+			chars: 0,
+			unmappable: options.unmappable,
+			affected: BTreeMap::new(),
+		}
+	}
+
+	/* Encodes the part of the text that starts at byte `offset`, counting the
+	 * characters it substitutes */
+	fn encode(&mut self, text: &str, offset: usize) -> Result<Vec<u8>, UnmappableError> {
+		self.chars += text.chars().count();
+
+		self.to
+			.encode_with(text, |error| {
+				let substitute = self.unmappable.substitute(error.ch).ok_or(error)?;
+				*self.affected.entry(error.ch).or_insert(0) += 1;
+				Ok(substitute)
+			})
+			.map_err(|error| UnmappableError {
+				offset: error.offset + offset,
+				ch: error.ch,
+				encoding: error.encoding,
+			})
+	}
+
+	pub fn affected_count(&self) -> usize {
+		self.affected.values().sum()
+	}
+}
+
+impl Display for Summary {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"Converted {} characters from {} to {}, {} unmappable ({})",
+			self.chars,
+			self.from,
+			self.to,
+			self.affected_count(),
+			self.unmappable
+		)?;
+
+		for (ch, count) in &self.affected {
+			write!(f, "\n\tU+{:04X} {ch:?}: {count}", *ch as u32)?;
+		}
+
+		Ok(())
+	}
+}
+
+/* A character the target encoding has no bytes for. Once decoded, it's no
+ * longer known which input bytes a character came from, so the offset is in
+ * the decoded text as UTF-8. */
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("'{ch}' at byte {offset} of the decoded text can't be encoded in {encoding}")]
+pub struct UnmappableError {
+	pub offset: usize,
+	pub ch: char,
+	pub encoding: Encoding,
+}
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscodeError {
+	#[error(transparent)]
+	Decode(#[from] DecodeError),
+	#[error(transparent)]
+	Encode(#[from] UnmappableError),
+}
+
+/* A byte order mark in the input is dropped, and none is written */
+pub fn transcode(bytes: &[u8], options: &Options) -> Result<(Vec<u8>, Summary), TranscodeError> {
+	let from = options.from.unwrap_or_else(|| detect::guess(bytes));
+	let text = from.decode(bytes)?;
+
+	let mut summary = Summary::new(from, options);
+	let output = summary.encode(&text, 0)?;
+
+	Ok((output, summary))
+}
+
+/* Like `transcode`, but reads and writes a chunk at a time. Without a source
+ * encoding, it's detected from the first chunk. Stateful encodings start over
+ * in every chunk, which adds a few escape sequences to ISO-2022-JP, and ends
+ * UTF-7's base64 runs early, but decodes to the same text. */
+pub fn transcode_stream(
+	mut reader: impl Read,
+	mut writer: impl Write,
+	options: &Options,
+) -> Result<Summary, StreamError> {
+	let mut buffer = vec![0; CHUNK_SIZE];
+	let mut len = stream::read_chunk(&mut reader, &mut buffer)?;
+
+	let from = options
+		.from
+		.unwrap_or_else(|| detect::guess_start(&buffer[..len]));
+	let mut decoder = StreamDecoder::new(from);
+	let mut summary = Summary::new(from, options);
+	let mut text = String::new();
+	let mut offset = 0;
+
+	loop {
+		text.clear();
+		decoder.decode(&buffer[..len], len == 0, &mut text)?;
+		writer.write_all(&summary.encode(&text, offset)?)?;
+		offset += text.len();
+
+		if len == 0 {
+			writer.flush()?;
+			return Ok(summary);
+		}
+		len = stream::read_chunk(&mut reader, &mut buffer)?;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::encoding::{CodePage, MultiByte};
+
+	fn options(from: Option<Encoding>, to: Encoding, unmappable: Unmappable) -> Options {
+		Options {
+			from,
+			to,
+			unmappable,
+		}
+	}
+
+	#[test]
+	fn test_transcode() {
+		let latin1 = options(Some(Encoding::Utf8), Encoding::Latin1, Unmappable::Error);
+		let (bytes, summary) = transcode("café".as_bytes(), &latin1).unwrap();
+		assert_eq!(bytes, b"caf\xe9");
+		assert_eq!(summary.chars, 4);
+		assert_eq!(summary.affected_count(), 0);
+
+		let detected = options(None, Encoding::Utf8, Unmappable::Error);
+		let (bytes, summary) = transcode(b"\xfe\xff\x00\xe9", &detected).unwrap();
+		assert_eq!(bytes, "é".as_bytes());
+		assert_eq!(summary.from, Encoding::Utf16Be);
+
+		let sjis = options(
+			None,
+			Encoding::MultiByte(MultiByte::ShiftJis),
+			Unmappable::Error,
+		);
+		let (bytes, _) = transcode("日本".as_bytes(), &sjis).unwrap();
+		assert_eq!(bytes, b"\x93\xfa\x96\x7b");
+	}
+
+	#[test]
+	fn test_unmappable() {
+		let text = "€5 – “ok” €".as_bytes();
+		let to = Encoding::SingleByte(CodePage::Iso8859_2);
+		let run = |unmappable| transcode(text, &options(None, to, unmappable));
+
+		assert_eq!(
+			run(Unmappable::Error),
+			Err(TranscodeError::Encode(UnmappableError {
+				offset: 0,
+				ch: '€',
+				encoding: to,
+			}))
+		);
+
+		let (bytes, summary) = run(Unmappable::Replace).unwrap();
+		assert_eq!(bytes, b"?5 ? ?ok? ?");
+		assert_eq!(summary.affected_count(), 5);
+		assert_eq!(summary.affected[&'€'], 2);
+
+		let (bytes, _) = run(Unmappable::HtmlReference).unwrap();
+		assert_eq!(bytes, b"&#8364;5 &#8211; &#8220;ok&#8221; &#8364;");
+
+		let (bytes, _) = run(Unmappable::Transliterate).unwrap();
+		assert_eq!(bytes, b"EUR5 - \"ok\" EUR");
+
+		let emoji = options(None, to, Unmappable::Transliterate);
+		let (bytes, summary) = transcode("ok 😀".as_bytes(), &emoji).unwrap();
+		assert_eq!(bytes, b"ok ?");
+		assert_eq!(summary.affected[&'😀'], 1);
+	}
+
+	#[test]
+	fn test_unmappable_offset() {
+		/* At byte 3 of the decoded text, but byte 6 of the UTF-16 input */
+		let text = Encoding::Utf16Le.encode("caf€").unwrap();
+		let to = Encoding::Latin1;
+		let error = transcode(
+			&text,
+			&options(Some(Encoding::Utf16Le), to, Unmappable::Error),
+		)
+		.unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			"'€' at byte 3 of the decoded text can't be encoded in ISO-8859-1"
+		);
+
+		let mut summary = Summary::new(Encoding::Utf8, &options(None, to, Unmappable::Error));
+		assert!(summary.encode("€ and ™", 0).is_err());
+		assert_eq!(summary.affected_count(), 0);
+	}
+
+	#[test]
+	fn test_transcode_stream() {
+		let text = "“ok” ".repeat(CHUNK_SIZE / 2);
+		let to = Encoding::Latin1;
+
+		let mut out = Vec::new();
+		let html = options(None, to, Unmappable::HtmlReference);
+		let summary = transcode_stream(text.as_bytes(), &mut out, &html).unwrap();
+		assert_eq!(out, "&#8220;ok&#8221; ".repeat(CHUNK_SIZE / 2).as_bytes());
+		assert_eq!(summary.from, Encoding::Utf8);
+		assert_eq!(summary.chars, CHUNK_SIZE / 2 * 5);
+		assert_eq!(summary.affected_count(), CHUNK_SIZE);
+
+		let tail = format!("{text}€");
+		let error = options(None, to, Unmappable::Error);
+		assert!(matches!(
+			transcode_stream(tail.as_bytes(), std::io::sink(), &error),
+			Err(StreamError::Encode(UnmappableError { offset, ch: '“', .. })) if offset == 0
+		));
+		let plain = format!("{}€", "ok ".repeat(CHUNK_SIZE));
+		assert!(matches!(
+			transcode_stream(plain.as_bytes(), std::io::sink(), &error),
+			Err(StreamError::Encode(UnmappableError { offset, ch: '€', .. })) if offset == plain.len() - 3
+		));
+	}
+
+	#[test]
+	fn test_parse_unmappable() {
+		for policy in Unmappable::ALL {
+			assert_eq!(policy.name().parse(), Ok(policy));
+		}
+		assert!("ignore".parse::<Unmappable>().is_err());
+	}
+}
@@ -0,0 +1,204 @@
+use std::char::decode_utf16;
+
+use super::{DecodeError, Encoding};
+
+/* Characters that are written as themselves: RFC 2152's set D plus
+ * whitespace. Everything else is written in modified base64 after a '+'. */
+fn is_direct(ch: char) -> bool {
+	ch.is_ascii_alphanumeric() || " \t\r\n'(),-./:?".contains(ch)
+}
+
+fn base64_value(b: u8) -> Option<u32> {
+	match b {
+		b'A'..=b'Z' => Some((b - b'A') as u32),
+		b'a'..=b'z' => Some((b - b'a') as u32 + 26),
+		b'0'..=b'9' => Some((b - b'0') as u32 + 52),
+		b'+' => Some(62),
+		b'/' => Some(63),
+		_ => None,
+	}
+}
+
+fn base64_digit(value: u32) -> u8 {
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"[value as usize]
+}
+
+/* How much of `bytes` can be decoded on its own: everything up to a base64 run
+ * that may continue in the bytes that follow */
+pub fn complete_len(bytes: &[u8]) -> usize {
+	let mut run = None;
+
+	for (i, &b) in bytes.iter().enumerate() {
+		match run {
+			None if b == b'+' => run = Some(i),
+			Some(_) if base64_value(b).is_none() => run = None,
+			_ => {}
+		}
+	}
+
+	run.unwrap_or(bytes.len())
+}
+
+/* Where the unfinished base64 run that starts at `start` can be cut, so what
+ * comes before decodes on its own: after a multiple of 8 digits, which hold
+ * exactly 3 code units, and not right after a high surrogate. At least one
+ * digit is left, so a '-' that follows still ends the run once it's resumed
+ * with a '+' of its own. */
+pub fn split_run(bytes: &[u8], start: usize) -> Option<usize> {
+	let digits = &bytes[start + 1..];
+
+	(1..=digits.len().saturating_sub(1) / 8)
+		.rev()
+		.find(|&groups| {
+			let value = |i| base64_value(digits[(groups - 1) * 8 + i]).unwrap();
+			let last_unit = (value(5) & 0xF) << 12 | value(6) << 6 | value(7);
+			!(0xD800..=0xDBFF).contains(&last_unit)
+		})
+		.map(|groups| start + 1 + groups * 8)
+}
+
+pub fn decode(bytes: &[u8]) -> Result<String, DecodeError> {
+	let malformed = |offset| DecodeError::Malformed {
+		offset,
+		encoding: Encoding::Utf7,
+	};
+
+	/* UTF-16 code units with the offset of the base64 digit they ended in */
+	let mut units = Vec::new();
+	let mut i = 0;
+
+	while i < bytes.len() {
+		match bytes[i] {
+			b'+' if bytes.get(i + 1) == Some(&b'-') => {
+				units.push((i, b'+' as u16));
+				i += 2;
+			}
+			b'+' => {
+				i += 1;
+				let (mut bits, mut len) = (0u32, 0);
+
+				while let Some(value) = bytes.get(i).copied().and_then(base64_value) {
+					bits = (bits << 6) | value;
+					len += 6;
+					if len >= 16 {
+						len -= 16;
+						units.push((i, (bits >> len) as u16));
+						bits &= (1 << len) - 1;
+					}
+					i += 1;
+				}
+
+				/* Whatever is left over is padding, and has to be zero */
+				if len >= 6 || bits != 0 {
+					return Err(malformed(i - 1));
+				}
+
+				if bytes.get(i) == Some(&b'-') {
+					i += 1;
+				}
+			}
+			b @ 0x00..=0x7F => {
+				units.push((i, b as u16));
+				i += 1;
+			}
+			_ => return Err(malformed(i)),
+		}
+	}
+
+	let mut text = String::with_capacity(units.len());
+	let mut offsets = units.iter().map(|&(offset, _)| offset);
+	for result in decode_utf16(units.iter().map(|&(_, unit)| unit)) {
+		let offset = offsets.next().unwrap();
+		match result {
+			Ok(ch) => {
+				if ch.len_utf16() == 2 {
+					offsets.next();
+				}
+				text.push(ch);
+			}
+			Err(err) => {
+				return Err(DecodeError::UnpairedSurrogate {
+					offset,
+					unit: err.unpaired_surrogate(),
+				});
+			}
+		}
+	}
+
+	Ok(text)
+}
+
+pub fn encode(text: &str) -> Vec<u8> {
+	let mut bytes = Vec::with_capacity(text.len());
+	let mut chars = text.chars().peekable();
+
+	while let Some(ch) = chars.next() {
+		if ch == '+' {
+			bytes.extend_from_slice(b"+-");
+		} else if is_direct(ch) {
+			bytes.push(ch as u8);
+		} else {
+			bytes.push(b'+');
+			let (mut bits, mut len) = (0u32, 0);
+
+			let mut encode = |ch: char| {
+				for unit in ch.encode_utf16(&mut [0; 2]) {
+					bits = (bits << 16) | *unit as u32;
+					len += 16;
+					while len >= 6 {
+						len -= 6;
+						bytes.push(base64_digit((bits >> len) & 0x3F));
+					}
+					bits &= (1 << len) - 1;
+				}
+			};
+
+			encode(ch);
+			while let Some(&ch) = chars.peek() {
+				if ch == '+' || is_direct(ch) {
+					break;
+				}
+				encode(ch);
+				chars.next();
+			}
+
+			if len > 0 {
+				bytes.push(base64_digit((bits << (6 - len)) & 0x3F));
+			}
+			bytes.push(b'-');
+		}
+	}
+
+	bytes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode() {
+		assert_eq!(decode(b"Hi Mom -+Jjo--!").as_deref(), Ok("Hi Mom -☺-!"));
+		assert_eq!(decode(b"+ZeVnLIqe-").as_deref(), Ok("日本語"));
+		assert_eq!(decode(b"1 +- 1").as_deref(), Ok("1 + 1"));
+		assert_eq!(decode(b"+2D3eAA-").as_deref(), Ok("😀"));
+		assert_eq!(decode(b"+/v8-a").as_deref(), Ok("\u{FEFF}a"));
+		assert_eq!(
+			decode(b"a+AGEA"),
+			Err(DecodeError::Malformed {
+				offset: 5,
+				encoding: Encoding::Utf7
+			})
+		);
+		assert!(decode(b"caf\xe9").is_err());
+	}
+
+	#[test]
+	fn test_round_trip() {
+		for text in ["Hi Mom -☺-!", "1 + 1 = 2", "日本語", "😀 émoji", "A≢Α."] {
+			let bytes = encode(text);
+			assert!(bytes.is_ascii());
+			assert_eq!(decode(&bytes).as_deref(), Ok(text), "{text}");
+		}
+	}
+}
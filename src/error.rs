@@ -0,0 +1,199 @@
+use std::{fmt::Display, str::FromStr};
+
+use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Line {line}, column {column}: {message} ('{text}')")]
+pub struct InputError {
+	pub line: usize,
+	pub column: usize,
+	pub text: String,
+	pub message: String,
+}
+
+impl InputError {
+	/* For input that is wrong as a whole. Points past the end of the last line. */
+	pub fn at_end(input: &str, message: impl Display) -> InputError {
+		let line = numbered_lines(input).last().unwrap_or(Line {
+			number: 1,
+			text: "",
+		});
+
+		line.error_at_offset(line.text.len(), "", message)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+	pub number: usize,
+	pub text: &'a str,
+}
+
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+	input.lines().enumerate().map(|(i, text)| Line {
+		number: i + 1,
+		text,
+	})
+}
+
+impl<'a> Line<'a> {
+	/* `at` should be a slice of this line. Anything else points at the start
+	 * of the line. */
+	pub fn error(&self, at: &str, message: impl Display) -> InputError {
+		let offset = (at.as_ptr() as usize)
+			.checked_sub(self.text.as_ptr() as usize)
+			.filter(|offset| offset + at.len() <= self.text.len())
+			.unwrap_or(0);
+
+		self.error_at_offset(offset, at, message)
+	}
+
+	pub fn error_at_offset(&self, offset: usize, text: &str, message: impl Display) -> InputError {
+		InputError {
+			line: self.number,
+			column: self.text[..offset].chars().count() + 1,
+			text: text.to_string(),
+			message: message.to_string(),
+		}
+	}
+
+	pub fn end_error(&self, what: &str) -> InputError {
+		self.error_at_offset(self.text.len(), "", format!("Expected {what}"))
+	}
+
+	pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), InputError> {
+		self.text
+			.split_once(delimiter)
+			.ok_or_else(|| self.error(self.text, format!("Expected '{delimiter}'")))
+	}
+
+	pub fn parse<T>(&self, at: &str) -> Result<T, InputError>
+	where
+		T: FromStr,
+		T::Err: Display,
+	{
+		at.parse().map_err(|err| self.error(at, err))
+	}
+
+	pub fn nom_error(&self, err: nom::Err<NomError<'_>>) -> InputError {
+		match err {
+			nom::Err::Error(err) | nom::Err::Failure(err) => {
+				let text = err.input.split_whitespace().next().unwrap_or("");
+				let offset = self.text.len() - err.input.len();
+				self.error_at_offset(offset, text, err)
+			}
+			nom::Err::Incomplete(_) => self.error(self.text, "Incomplete input"),
+		}
+	}
+}
+
+/* nom error that remembers what was expected, so failures can be reported as
+ * something more useful than an `ErrorKind`. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NomError<'a> {
+	pub input: &'a str,
+	pub kind: ErrorKind,
+	pub context: Option<&'static str>,
+	pub cause: Option<String>,
+}
+
+impl Display for NomError<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match (self.context, &self.cause) {
+			(Some(context), Some(cause)) => write!(f, "Expected {context}: {cause}"),
+			(Some(context), None) => write!(f, "Expected {context}"),
+			(None, Some(cause)) => write!(f, "{cause}"),
+			(None, None) if self.input.is_empty() => write!(f, "Unexpected end of line"),
+			(None, None) => write!(f, "Unexpected input ({})", self.kind.description()),
+		}
+	}
+}
+
+impl<'a> ParseError<&'a str> for NomError<'a> {
+	fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+		Self {
+			input,
+			kind,
+			context: None,
+			cause: None,
+		}
+	}
+
+	fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+		other
+	}
+}
+
+impl<'a> ContextError<&'a str> for NomError<'a> {
+	fn add_context(_: &'a str, context: &'static str, other: Self) -> Self {
+		Self {
+			context: other.context.or(Some(context)),
+			..other
+		}
+	}
+}
+
+impl<'a, E: Display> FromExternalError<&'a str, E> for NomError<'a> {
+	fn from_external_error(input: &'a str, kind: ErrorKind, e: E) -> Self {
+		Self {
+			input,
+			kind,
+			context: None,
+			cause: Some(e.to_string()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_error_column() {
+		let line = Line {
+			number: 3,
+			text: "Ödön: 12x",
+		};
+
+		let err = line.error(&line.text[8..], "Invalid number");
+		assert_eq!(
+			err,
+			InputError {
+				line: 3,
+				column: 7,
+				text: "12x".to_string(),
+				message: "Invalid number".to_string(),
+			}
+		);
+		assert_eq!(err.to_string(), "Line 3, column 7: Invalid number ('12x')");
+	}
+
+	#[test]
+	fn test_error_outside_line() {
+		let line = Line {
+			number: 1,
+			text: "abc",
+		};
+
+		assert_eq!(line.error("other", "Oops").column, 1);
+	}
+
+	#[test]
+	fn test_at_end() {
+		let err = InputError::at_end("ab\n\nÖdön\n", "Expected more");
+		assert_eq!((err.line, err.column), (3, 5));
+		assert_eq!(InputError::at_end("", "Expected more").line, 1);
+	}
+
+	#[test]
+	fn test_parse() {
+		let line = Line {
+			number: 2,
+			text: "a 12b",
+		};
+
+		let err = line.parse::<u32>(&line.text[2..]).unwrap_err();
+		assert_eq!((err.line, err.column), (2, 3));
+		assert_eq!(err.text, "12b");
+	}
+}
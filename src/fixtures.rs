@@ -0,0 +1,64 @@
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+};
+
+pub const INPUT_EXTENSION: &str = "input";
+pub const ANSWER_EXTENSION: &str = "answer";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+	pub day: u8,
+	pub name: String,
+	pub path: PathBuf,
+	pub input: String,
+	pub answer: Option<String>,
+}
+
+pub fn default_dir() -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/* Fixtures live in one directory per day, e.g. `01/example.input`, with the
+ * expected answer next to it in `01/example.answer`. */
+pub fn load(dir: &Path) -> io::Result<Vec<Fixture>> {
+	let mut fixtures = Vec::new();
+
+	for day_dir in fs::read_dir(dir)? {
+		let day_dir = day_dir?.path();
+		let Some(day) = day_dir
+			.file_name()
+			.and_then(|name| name.to_str())
+			.and_then(|name| name.parse().ok())
+		else {
+			continue;
+		};
+
+		for path in fs::read_dir(&day_dir)? {
+			let path = path?.path();
+			if path.extension().is_none_or(|ext| ext != INPUT_EXTENSION) {
+				continue;
+			}
+
+			let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+			let input = fs::read_to_string(&path)?;
+			let answer = match fs::read_to_string(path.with_extension(ANSWER_EXTENSION)) {
+				Ok(answer) => Some(answer.trim_end().to_string()),
+				Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+				Err(err) => return Err(err),
+			};
+
+			fixtures.push(Fixture {
+				day,
+				name,
+				path,
+				input,
+				answer,
+			});
+		}
+	}
+
+	fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+
+	Ok(fixtures)
+}
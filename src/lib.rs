@@ -1,2 +1,13 @@
+pub mod bench;
+pub mod bytes;
 pub mod crossword;
+pub mod days;
+pub mod encoding;
+pub mod error;
+pub mod fixtures;
+pub mod puzzle;
+pub mod text;
 pub mod utf8;
+
+pub use error::InputError;
+pub use puzzle::Puzzle;
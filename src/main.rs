@@ -0,0 +1,319 @@
+use std::{
+	env,
+	error::Error,
+	fs::{self, File},
+	io::{Read, Write, read_to_string, stdin, stdout},
+	path::{Path, PathBuf},
+	process::ExitCode,
+	time::{Duration, Instant},
+};
+
+use i18n_puzzles::{
+	InputError,
+	bench::{self, BenchResult},
+	days::{self, DAYS, Day, Solver},
+	encoding::{
+		Encoding,
+		transcode::{self, Unmappable},
+	},
+	fixtures,
+};
+
+const USAGE: &str = "Usage:
+	i18n-puzzles run <day> [--input <file>]
+	i18n-puzzles run --all [--inputs <directory>]
+	i18n-puzzles bench [<day>] [--fixtures <directory>] [--scale <factors>] [--min-time <ms>]
+	i18n-puzzles transcode --to <encoding> [--from <encoding>] [--unmappable <policy>]
+		[--input <file>] [--output <file>]
+
+Without --input, the puzzle input is read from stdin.
+With --all, every day reads <directory>/<day>.txt, where <directory> defaults to 'inputs'.
+bench times parsing and solving every fixture, also scaled up by each of the comma separated
+factors (default 1,2,4,8).
+transcode detects the source encoding when --from is missing, reads stdin and writes stdout by
+default, and reports the characters it couldn't encode on stderr. <policy> is one of error
+(the default), replace, html or transliterate.";
+
+const DEFAULT_INPUTS: &str = "inputs";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+	Run {
+		day: u8,
+		input: Option<PathBuf>,
+	},
+	RunAll {
+		inputs: PathBuf,
+	},
+	Bench {
+		day: Option<u8>,
+		fixtures: PathBuf,
+		options: bench::Options,
+	},
+	Transcode {
+		options: transcode::Options,
+		input: Option<PathBuf>,
+		output: Option<PathBuf>,
+	},
+}
+
+fn main() -> ExitCode {
+	let command = match parse_args(env::args().skip(1)) {
+		Ok(command) => command,
+		Err(err) => {
+			eprintln!("{err}\n\n{USAGE}");
+			return ExitCode::FAILURE;
+		}
+	};
+
+	let result = match command {
+		Command::Run { day, input } => run_day(day, input.as_deref()),
+		Command::RunAll { inputs } => run_all(&inputs),
+		Command::Bench {
+			day,
+			fixtures,
+			options,
+		} => run_bench(day, &fixtures, &options),
+		Command::Transcode {
+			options,
+			input,
+			output,
+		} => run_transcode(&options, input.as_deref(), output.as_deref()),
+	};
+
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(err) => {
+			eprintln!("Error: {err}");
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	match args.next().as_deref() {
+		Some("run") => {}
+		Some("bench") => return parse_bench_args(args),
+		Some("transcode") => return parse_transcode_args(args),
+		Some(other) => return Err(format!("Unknown command '{other}'")),
+		None => return Err("Missing command".to_string()),
+	}
+
+	let mut day = None;
+	let mut all = false;
+	let mut path = None;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--all" => all = true,
+			"--input" | "--inputs" => {
+				let value = args
+					.next()
+					.ok_or_else(|| format!("Missing value for {arg}"))?;
+				path = Some(PathBuf::from(value));
+			}
+			day_arg if day.is_none() && !day_arg.starts_with('-') => {
+				let value = day_arg
+					.parse::<u8>()
+					.map_err(|_| format!("Invalid day '{day_arg}'"))?;
+				day = Some(value);
+			}
+			other => return Err(format!("Unexpected argument '{other}'")),
+		}
+	}
+
+	match (day, all) {
+		(Some(day), false) => Ok(Command::Run { day, input: path }),
+		(None, true) => Ok(Command::RunAll {
+			inputs: path.unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS)),
+		}),
+		(Some(_), true) => Err("Specify either a day or --all, not both".to_string()),
+		(None, false) => Err("Missing day".to_string()),
+	}
+}
+
+fn parse_bench_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut day = None;
+	let mut fixtures = fixtures::default_dir();
+	let mut options = bench::Options::default();
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--fixtures" | "--scale" | "--min-time" => {
+				let value = args
+					.next()
+					.ok_or_else(|| format!("Missing value for {arg}"))?;
+
+				match arg.as_str() {
+					"--fixtures" => fixtures = PathBuf::from(value),
+					"--scale" => {
+						options.factors = value
+							.split(',')
+							.map(|factor| match factor.trim().parse::<usize>() {
+								Ok(factor) if factor > 0 => Ok(factor),
+								_ => Err(format!("Invalid scale factor '{factor}'")),
+							})
+							.collect::<Result<_, _>>()?;
+					}
+					_ => {
+						let millis = value
+							.parse::<u64>()
+							.map_err(|_| format!("Invalid minimum time '{value}'"))?;
+						options.min_time = Duration::from_millis(millis);
+					}
+				}
+			}
+			day_arg if day.is_none() && !day_arg.starts_with('-') => {
+				let value = day_arg
+					.parse::<u8>()
+					.map_err(|_| format!("Invalid day '{day_arg}'"))?;
+				day = Some(value);
+			}
+			other => return Err(format!("Unexpected argument '{other}'")),
+		}
+	}
+
+	Ok(Command::Bench {
+		day,
+		fixtures,
+		options,
+	})
+}
+
+fn parse_transcode_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut from = None;
+	let mut to = None;
+	let mut unmappable = Unmappable::default();
+	let mut input = None;
+	let mut output = None;
+
+	while let Some(arg) = args.next() {
+		let value = match arg.as_str() {
+			"--from" | "--to" | "--unmappable" | "--input" | "--output" => args
+				.next()
+				.ok_or_else(|| format!("Missing value for {arg}"))?,
+			other => return Err(format!("Unexpected argument '{other}'")),
+		};
+		let encoding = |label: &str| {
+			Encoding::for_label(label).ok_or_else(|| format!("Unknown encoding '{label}'"))
+		};
+
+		match arg.as_str() {
+			"--from" => from = Some(encoding(&value)?),
+			"--to" => to = Some(encoding(&value)?),
+			"--unmappable" => unmappable = value.parse()?,
+			"--input" => input = Some(PathBuf::from(value)),
+			_ => output = Some(PathBuf::from(value)),
+		}
+	}
+
+	Ok(Command::Transcode {
+		options: transcode::Options {
+			from,
+			to: to.ok_or("Missing --to")?,
+			unmappable,
+		},
+		input,
+		output,
+	})
+}
+
+fn run_day(day: u8, input: Option<&Path>) -> Result<(), Box<dyn Error>> {
+	let solver = days::solver(day).ok_or_else(|| format!("No solver for day {day}"))?;
+
+	let input = match input {
+		Some(path) => fs::read_to_string(path)?,
+		None => read_to_string(stdin())?,
+	};
+
+	run(day, solver, &input)?;
+
+	Ok(())
+}
+
+fn run_all(inputs: &Path) -> Result<(), Box<dyn Error>> {
+	let mut failed = false;
+
+	for Day {
+		number: day,
+		solve: solver,
+		..
+	} in DAYS
+	{
+		let path = inputs.join(format!("{day:02}.txt"));
+		if !path.exists() {
+			eprintln!("Day {day:02}: skipped, no input at {}", path.display());
+			continue;
+		}
+
+		let input = fs::read_to_string(&path)?;
+		if let Err(err) = run(day, solver, &input) {
+			eprintln!("Day {day:02}: {err}");
+			failed = true;
+		}
+	}
+
+	if failed {
+		Err("Not every day could be solved".into())
+	} else {
+		Ok(())
+	}
+}
+
+fn run(day: u8, solver: Solver, input: &str) -> Result<(), InputError> {
+	let start = Instant::now();
+	let answer = solver(input)?;
+	let elapsed = start.elapsed();
+
+	println!("Day {day:02}: {answer} ({elapsed:.2?})");
+
+	Ok(())
+}
+
+fn run_bench(
+	day: Option<u8>,
+	fixtures: &Path,
+	options: &bench::Options,
+) -> Result<(), Box<dyn Error>> {
+	let selected = match day {
+		Some(day) => vec![days::get(day).ok_or_else(|| format!("No solver for day {day}"))?],
+		None => DAYS.to_vec(),
+	};
+
+	let fixtures = fixtures::load(fixtures)?;
+
+	println!("{}", BenchResult::HEADER);
+
+	for day in selected {
+		for fixture in fixtures.iter().filter(|fixture| fixture.day == day.number) {
+			let results = bench::bench(&day, fixture, options)
+				.map_err(|err| format!("{}: {err}", fixture.path.display()))?;
+
+			for result in results {
+				println!("{result}");
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn run_transcode(
+	options: &transcode::Options,
+	input: Option<&Path>,
+	output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+	let reader: Box<dyn Read> = match input {
+		Some(path) => Box::new(File::open(path)?),
+		None => Box::new(stdin().lock()),
+	};
+	let writer: Box<dyn Write> = match output {
+		Some(path) => Box::new(File::create(path)?),
+		None => Box::new(stdout().lock()),
+	};
+
+	let summary = transcode::transcode_stream(reader, writer, options)?;
+	eprintln!("{summary}");
+
+	Ok(())
+}
@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+use crate::InputError;
+
+pub trait Puzzle {
+	type Input;
+	type Answer: Display;
+
+	fn parse(input: &str) -> Result<Self::Input, InputError>;
+
+	fn solve(input: &Self::Input) -> Self::Answer;
+
+	fn run(input: &str) -> Result<Self::Answer, InputError> {
+		Self::parse(input).map(|input| Self::solve(&input))
+	}
+
+	/* Grows `input` to roughly `factor` times its size while keeping it a
+	 * valid puzzle input, for benchmarking. `None` if the puzzle has no
+	 * sensible way to grow. */
+	fn scale(_input: &str, _factor: usize) -> Option<String> {
+		None
+	}
+}
+
+pub fn repeat_lines(input: &str, factor: usize) -> String {
+	let mut result = String::with_capacity(input.len() * factor + factor);
+
+	for _ in 0..factor {
+		for line in input.lines() {
+			result.push_str(line);
+			result.push('\n');
+		}
+	}
+
+	result
+}
+
+/* Keeps everything up to and including the first empty line as-is, and
+ * repeats the lines after it. */
+pub fn repeat_last_section(input: &str, factor: usize) -> String {
+	let (head, tail) = input.split_once("\n\n").unwrap_or(("", input));
+	let mut result = String::with_capacity(input.len() * factor);
+
+	if !head.is_empty() {
+		result.push_str(head);
+		result.push_str("\n\n");
+	}
+	result.push_str(&repeat_lines(tail, factor));
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_repeat_lines() {
+		assert_eq!(repeat_lines("a\nb", 2), "a\nb\na\nb\n");
+		assert_eq!(repeat_lines("a\nb\n", 1), "a\nb\n");
+		assert_eq!(repeat_lines("a\n", 0), "");
+	}
+
+	#[test]
+	fn test_repeat_last_section() {
+		assert_eq!(repeat_last_section("a\nb\n\nc\n", 3), "a\nb\n\nc\nc\nc\n");
+		assert_eq!(repeat_last_section("c", 2), "c\nc\n");
+	}
+}
@@ -0,0 +1,142 @@
+pub mod fold;
+pub mod sms;
+pub mod truncate;
+pub mod tweet;
+
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/* What the length of a text is counted in */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+	Bytes,
+	#[default]
+	CodePoints,
+	/* Extended grapheme clusters as defined by UAX #29, what a reader would
+	 * call a character: a flag, an emoji ZWJ sequence or e + ◌́ are one each */
+	Graphemes,
+	Utf16,
+}
+
+impl Unit {
+	pub const ALL: [Unit; 4] = [Unit::Bytes, Unit::CodePoints, Unit::Graphemes, Unit::Utf16];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Unit::Bytes => "bytes",
+			Unit::CodePoints => "code-points",
+			Unit::Graphemes => "graphemes",
+			Unit::Utf16 => "utf-16",
+		}
+	}
+
+	pub fn len(self, text: &str) -> usize {
+		match self {
+			Unit::Bytes => text.len(),
+			Unit::CodePoints => text.chars().count(),
+			Unit::Graphemes => graphemes(text).count(),
+			Unit::Utf16 => text.encode_utf16().count(),
+		}
+	}
+
+	/* Where each unit of `text` is, by index. A byte or UTF-16 unit is part
+	 * of a character, so that whole character is its span, once for every unit
+	 * in it. */
+	pub fn spans(self, text: &str) -> Vec<Range<usize>> {
+		let spans = |unit_len: fn(char) -> usize| {
+			text.char_indices()
+				.flat_map(|(offset, ch)| {
+					let span = offset..offset + ch.len_utf8();
+					std::iter::repeat_n(span, unit_len(ch))
+				})
+				.collect()
+		};
+
+		match self {
+			Unit::Bytes => spans(char::len_utf8),
+			Unit::CodePoints => spans(|_| 1),
+			Unit::Graphemes => text
+				.grapheme_indices(true)
+				.map(|(offset, grapheme)| offset..offset + grapheme.len())
+				.collect(),
+			Unit::Utf16 => spans(char::len_utf16),
+		}
+	}
+
+	/* The text of the unit at `index`, as in `spans` */
+	pub fn nth(self, text: &str, index: usize) -> Option<&str> {
+		match self {
+			Unit::Graphemes => graphemes(text).nth(index),
+			_ => self.spans(text).get(index).map(|span| &text[span.clone()]),
+		}
+	}
+}
+
+impl FromStr for Unit {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Unit::ALL
+			.into_iter()
+			.find(|unit| unit.name() == s)
+			.ok_or_else(|| format!("Unknown unit '{s}'"))
+	}
+}
+
+impl Display for Unit {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name())
+	}
+}
+
+/* The extended grapheme clusters of `text` */
+pub fn graphemes(text: &str) -> impl Iterator<Item = &str> {
+	text.graphemes(true)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const FAMILY: &str = "👨\u{200D}👩\u{200D}👧";
+
+	#[test]
+	fn test_len() {
+		let text = format!("e\u{301}🇳🇱{FAMILY}");
+		assert_eq!(Unit::Bytes.len(&text), 3 + 8 + 18);
+		assert_eq!(Unit::CodePoints.len(&text), 2 + 2 + 5);
+		assert_eq!(Unit::Graphemes.len(&text), 3);
+		assert_eq!(Unit::Utf16.len(&text), 2 + 4 + 8);
+	}
+
+	#[test]
+	fn test_graphemes() {
+		assert_eq!(
+			graphemes("ne\u{301}e\r\n🇳🇱🇧🇪").collect::<Vec<_>>(),
+			["n", "e\u{301}", "e", "\r\n", "🇳🇱", "🇧🇪"]
+		);
+		assert_eq!(graphemes(&format!("a{FAMILY}b")).count(), 3);
+		assert_eq!(graphemes("한국어").count(), 3);
+		assert_eq!(graphemes("\u{1100}\u{1161}\u{11A8}").count(), 1);
+	}
+
+	#[test]
+	fn test_nth() {
+		let text = "a💩e\u{301}";
+		assert_eq!(Unit::CodePoints.nth(text, 1), Some("💩"));
+		assert_eq!(Unit::CodePoints.nth(text, 3), Some("\u{301}"));
+		assert_eq!(Unit::Graphemes.nth(text, 2), Some("e\u{301}"));
+		assert_eq!(Unit::Utf16.nth(text, 2), Some("💩"));
+		assert_eq!(Unit::Utf16.nth(text, 3), Some("e"));
+		assert_eq!(Unit::Bytes.nth(text, 4), Some("💩"));
+		assert_eq!(Unit::Graphemes.nth(text, 3), None);
+	}
+
+	#[test]
+	fn test_parse_unit() {
+		for unit in Unit::ALL {
+			assert_eq!(unit.name().parse(), Ok(unit));
+		}
+	}
+}
@@ -0,0 +1,183 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+use crate::encoding::detect::Script;
+
+/* Letters that don't decompose into a base letter and accents, but are
+ * usually written as these when accents can't be */
+const FOLDS: [(char, &str); 27] = [
+	('Æ', "AE"),
+	('æ', "ae"),
+	('Đ', "D"),
+	('đ', "d"),
+	('Ð', "D"),
+	('ð', "d"),
+	('Ħ', "H"),
+	('ħ', "h"),
+	('ı', "i"),
+	('ĸ', "q"),
+	('Ŀ', "L"),
+	('ŀ', "l"),
+	('Ł', "L"),
+	('ł', "l"),
+	('Ŋ', "N"),
+	('ŋ', "n"),
+	('Ø', "O"),
+	('ø', "o"),
+	('Œ', "OE"),
+	('œ', "oe"),
+	('ſ', "s"),
+	('ẞ', "SS"),
+	('ß', "ss"),
+	('Þ', "TH"),
+	('þ', "th"),
+	('Ŧ', "T"),
+	('ŧ', "t"),
+];
+
+/* Languages that spell some letters out differently */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+	German,
+	Danish,
+	Norwegian,
+}
+
+impl Language {
+	fn overrides(self) -> &'static [(char, &'static str)] {
+		match self {
+			Language::German => &[
+				('Ä', "Ae"),
+				('ä', "ae"),
+				('Ö', "Oe"),
+				('ö', "oe"),
+				('Ü', "Ue"),
+				('ü', "ue"),
+			],
+			Language::Danish | Language::Norwegian => {
+				&[('Å', "Aa"), ('å', "aa"), ('Ø', "Oe"), ('ø', "oe")]
+			}
+		}
+	}
+}
+
+/* Replaces every letter by its base letters, so "Łódź" becomes "Lodz" */
+pub fn fold(text: &str) -> String {
+	fold_with(text, &[])
+}
+
+/* Like `fold`, with the spelling of `language`, so in German "Müller" becomes
+ * "Mueller" */
+pub fn fold_for(text: &str, language: Language) -> String {
+	fold_with(text, language.overrides())
+}
+
+/* Like `fold`, but every letter stays a single letter, the first of its base
+ * letters, so æ becomes a and ß becomes s. For checks that go letter by
+ * letter, like day 8's passwords, where spelling a letter out would count it
+ * twice. */
+pub fn fold_letters(text: &str) -> String {
+	let mut folded = String::with_capacity(text.len());
+	fold_each(text, &[], |letter| folded.extend(letter.chars().next()));
+	folded
+}
+
+fn fold_with(text: &str, overrides: &[(char, &str)]) -> String {
+	let mut folded = String::with_capacity(text.len());
+	fold_each(text, overrides, |letter| folded.push_str(letter));
+	folded
+}
+
+/* Passes what every character of `text` folds to to `push`. Accents are only
+ * dropped from Latin letters, as elsewhere they're part of the letter, like
+ * the dakuten of が or the breve of й. */
+fn fold_each(text: &str, overrides: &[(char, &str)], mut push: impl FnMut(&str)) {
+	let mut letter = String::new();
+	/* Whether the marks that follow belong to a Latin letter */
+	let mut latin = false;
+
+	/* Composed first, so an override for ä also applies to a + ◌̈ */
+	for ch in text.nfc() {
+		letter.clear();
+
+		let replacement = overrides
+			.iter()
+			.chain(&FOLDS)
+			.find(|&&(from, _)| from == ch)
+			.map(|&(_, to)| to);
+
+		match replacement {
+			Some(to) => {
+				latin = true;
+				letter.push_str(to);
+			}
+			/* A mark that didn't compose with the letter before it */
+			None if is_combining_mark(ch) => {
+				if !latin {
+					letter.push(ch);
+				}
+			}
+			None => {
+				let base = ch.nfd().next().unwrap_or(ch);
+				latin = Script::of(base) == Script::Latin;
+
+				match latin {
+					true => letter.extend(ch.nfd().filter(|&ch| !is_combining_mark(ch))),
+					false => letter.push(ch),
+				}
+			}
+		}
+
+		push(&letter);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fold() {
+		assert_eq!(fold("Crème brûlée"), "Creme brulee");
+		assert_eq!(fold("Łódź"), "Lodz");
+		assert_eq!(fold("Đorđe Ħamrun"), "Dorde Hamrun");
+		assert_eq!(fold("Øresund Straße"), "Oresund Strasse");
+		assert_eq!(fold("Æsir œuvre Þór"), "AEsir oeuvre THor");
+		assert_eq!(fold("n\u{303}o\u{328}\u{301}"), "no");
+		assert_eq!(fold("Ŋaŋ ſ ŀ Ŀ ĸ"), "Nan s l L q");
+		assert_eq!(fold("日本語 Привет"), "日本語 Привет");
+	}
+
+	#[test]
+	fn test_fold_other_scripts() {
+		assert_eq!(fold("한국어"), "한국어");
+		assert_eq!(fold("がぎぐ パン"), "がぎぐ パン");
+		assert_eq!(fold("Йй ёж"), "Йй ёж");
+		assert_eq!(fold("ΆΈ"), "ΆΈ");
+		assert_eq!(fold("м\u{301}"), "м\u{301}");
+		assert_eq!(fold_letters("한국 й"), "한국 й");
+	}
+
+	#[test]
+	fn test_fold_letters() {
+		assert_eq!(
+			fold_letters("Æsir œuvre Þór Straße"),
+			"Asir ouvre Tor Strase"
+		);
+		assert_eq!(fold_letters("Łódź"), "Lodz");
+		assert_eq!(fold_letters("e\u{301}\u{301}"), "e");
+	}
+
+	#[test]
+	fn test_fold_for() {
+		assert_eq!(
+			fold_for("Müller Straße", Language::German),
+			"Mueller Strasse"
+		);
+		assert_eq!(fold_for("Mu\u{308}ller", Language::German), "Mueller");
+		assert_eq!(
+			fold_for("Ålborg, Søren", Language::Danish),
+			"Aalborg, Soeren"
+		);
+		assert_eq!(fold_for("Ålborg, Søren", Language::German), "Alborg, Soren");
+	}
+}
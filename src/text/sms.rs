@@ -0,0 +1,169 @@
+/* How SMS messages are encoded and split into parts, following GSM 03.38 and
+ * 03.40. Carriers bill per part. */
+
+/* The GSM 7-bit default alphabet, minus the escape to the extension table */
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+	¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+
+/* The extension table, where every character takes an escape septet too */
+const GSM7_EXTENSION: &str = "\u{C}^{}\\[~]|€";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmsEncoding {
+	/* 7-bit septets */
+	Gsm7,
+	/* UTF-16 units really, as phones send emoji as surrogate pairs */
+	Ucs2,
+}
+
+impl SmsEncoding {
+	/* How many units fit in a message that's sent on its own */
+	pub fn single_capacity(self) -> usize {
+		match self {
+			SmsEncoding::Gsm7 => 160,
+			SmsEncoding::Ucs2 => 70,
+		}
+	}
+
+	/* How many units fit in one part of a concatenated message, after the
+	 * header that says how to put the parts back together */
+	pub fn part_capacity(self) -> usize {
+		match self {
+			SmsEncoding::Gsm7 => 153,
+			SmsEncoding::Ucs2 => 67,
+		}
+	}
+
+	/* How many units `ch` takes, if it can be encoded at all */
+	pub fn units(self, ch: char) -> Option<usize> {
+		match self {
+			SmsEncoding::Gsm7 if GSM7_BASIC.contains(ch) => Some(1),
+			SmsEncoding::Gsm7 if GSM7_EXTENSION.contains(ch) => Some(2),
+			SmsEncoding::Gsm7 => None,
+			SmsEncoding::Ucs2 => Some(ch.len_utf16()),
+		}
+	}
+
+	/* GSM-7 when it can encode every character of `text`, UCS-2 otherwise */
+	pub fn for_text(text: &str) -> SmsEncoding {
+		match text.chars().all(|ch| SmsEncoding::Gsm7.units(ch).is_some()) {
+			true => SmsEncoding::Gsm7,
+			false => SmsEncoding::Ucs2,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sms<'a> {
+	pub encoding: SmsEncoding,
+	/* Septets or UTF-16 units, escapes included */
+	pub units: usize,
+	/* What is sent in each part. A character is never split over two parts, so
+	 * a part can have room to spare. */
+	pub parts: Vec<&'a str>,
+}
+
+impl Sms<'_> {
+	pub fn cost(&self, price_per_part: u32) -> u32 {
+		self.parts.len() as u32 * price_per_part
+	}
+}
+
+/* Works out how `text` is sent. Even an empty message is one part. */
+pub fn segment(text: &str) -> Sms<'_> {
+	let encoding = SmsEncoding::for_text(text);
+	let units_of = |ch| {
+		encoding
+			.units(ch)
+			.expect("The encoding fits every character")
+	};
+	let units = text.chars().map(units_of).sum::<usize>();
+
+	if units <= encoding.single_capacity() {
+		return Sms {
+			encoding,
+			units,
+			parts: vec![text],
+		};
+	}
+
+	let mut parts = Vec::new();
+	let (mut start, mut used) = (0, 0);
+
+	for (offset, ch) in text.char_indices() {
+		let len = units_of(ch);
+		if used + len > encoding.part_capacity() {
+			parts.push(&text[start..offset]);
+			(start, used) = (offset, 0);
+		}
+		used += len;
+	}
+	parts.push(&text[start..]);
+
+	Sms {
+		encoding,
+		units,
+		parts,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_alphabet() {
+		assert_eq!(GSM7_BASIC.chars().count(), 127);
+		assert_eq!(
+			SmsEncoding::for_text("Hello, Ødön! 3 × 4"),
+			SmsEncoding::Ucs2
+		);
+		assert_eq!(
+			SmsEncoding::for_text("Hello, Ødèn! {€5}"),
+			SmsEncoding::Gsm7
+		);
+		assert_eq!(SmsEncoding::for_text("ça va"), SmsEncoding::Ucs2);
+		assert_eq!(SmsEncoding::for_text("Ça va"), SmsEncoding::Gsm7);
+	}
+
+	fn summary(text: &str) -> (SmsEncoding, usize, usize) {
+		let sms = segment(text);
+		(sms.encoding, sms.units, sms.parts.len())
+	}
+
+	#[test]
+	fn test_segment() {
+		assert_eq!(summary(""), (SmsEncoding::Gsm7, 0, 1));
+
+		assert_eq!(summary(&"a".repeat(160)), (SmsEncoding::Gsm7, 160, 1));
+		assert_eq!(summary(&"a".repeat(161)), (SmsEncoding::Gsm7, 161, 2));
+		assert_eq!(summary(&"a".repeat(306)), (SmsEncoding::Gsm7, 306, 2));
+		assert_eq!(summary(&"a".repeat(307)), (SmsEncoding::Gsm7, 307, 3));
+
+		assert_eq!(summary(&"ы".repeat(70)), (SmsEncoding::Ucs2, 70, 1));
+		assert_eq!(summary(&"ы".repeat(71)), (SmsEncoding::Ucs2, 71, 2));
+		assert_eq!(summary(&"ы".repeat(134)), (SmsEncoding::Ucs2, 134, 2));
+		assert_eq!(summary(&"😀".repeat(35)), (SmsEncoding::Ucs2, 70, 1));
+	}
+
+	#[test]
+	fn test_segment_keeps_characters_whole() {
+		assert_eq!(summary(&"€".repeat(80)), (SmsEncoding::Gsm7, 160, 1));
+
+		let text = format!("{}€", "a".repeat(152)).repeat(2);
+		assert_eq!(
+			segment(&text).parts,
+			[
+				"a".repeat(152),
+				format!("€{}", "a".repeat(151)),
+				"a€".to_string()
+			]
+		);
+
+		let text = "😀".repeat(36);
+		let sms = segment(&text);
+		let lengths = sms.parts.iter().map(|part| part.chars().count());
+		assert_eq!(lengths.collect::<Vec<_>>(), [33, 3]);
+		assert_eq!(sms.cost(5), 10);
+	}
+}
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{Unit, tweet};
+
+/* The most a text may measure */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+	Bytes(usize),
+	CodePoints(usize),
+	Graphemes(usize),
+	Utf16(usize),
+	/* As in `tweet::weighted_length` */
+	Weighted(usize),
+}
+
+impl Limit {
+	pub fn max(self) -> usize {
+		match self {
+			Limit::Bytes(max)
+			| Limit::CodePoints(max)
+			| Limit::Graphemes(max)
+			| Limit::Utf16(max)
+			| Limit::Weighted(max) => max,
+		}
+	}
+
+	pub fn measure(self, text: &str) -> usize {
+		match self {
+			Limit::Bytes(_) => Unit::Bytes.len(text),
+			Limit::CodePoints(_) => Unit::CodePoints.len(text),
+			Limit::Graphemes(_) => Unit::Graphemes.len(text),
+			Limit::Utf16(_) => Unit::Utf16.len(text),
+			Limit::Weighted(_) => tweet::weighted_length(text),
+		}
+	}
+
+	pub fn fits(self, text: &str) -> bool {
+		self.measure(text) <= self.max()
+	}
+}
+
+/* Cuts `text` down to fit `limit`, at a grapheme cluster boundary, so no UTF-8
+ * sequence is split, no character loses its accents and no emoji sequence is
+ * broken up. Text that is cut gets the ellipsis, and it counts towards the
+ * limit. When not even the ellipsis fits, nothing is left. Tweets are measured
+ * in NFC, so for a weighted limit the text is cut, and returned, in NFC. */
+pub fn truncate<'a>(text: &'a str, limit: Limit, ellipsis: Option<&str>) -> Cow<'a, str> {
+	let text = measured_form(text, limit);
+	let fits = |text: &str| match limit {
+		Limit::Weighted(max) => tweet::Config::default().weighted_length_nfc(text) <= max,
+		_ => limit.fits(text),
+	};
+
+	if fits(&text) {
+		return text;
+	}
+
+	let ellipsis = measured_form(ellipsis.unwrap_or_default(), limit);
+	let with_ellipsis = |end: usize| format!("{}{ellipsis}", &text[..end]);
+
+	/* A URL counts the same however long it is, so it's kept whole too */
+	let urls = match limit {
+		Limit::Weighted(_) => tweet::urls(&text),
+		_ => Vec::new(),
+	};
+
+	/* Every boundary but the end of the text, which doesn't fit */
+	let boundaries = text
+		.grapheme_indices(true)
+		.map(|(offset, _)| offset)
+		.filter(|&offset| {
+			!urls
+				.iter()
+				.any(|url| url.start < offset && offset < url.end)
+		})
+		.collect::<Vec<_>>();
+
+	/* Longer prefixes measure more, so the boundaries that fit come first */
+	let fitting = boundaries.partition_point(|&end| fits(&with_ellipsis(end)));
+	let Some(end) = fitting.checked_sub(1).map(|last| boundaries[last]) else {
+		return Cow::Borrowed("");
+	};
+
+	if !ellipsis.is_empty() {
+		return Cow::Owned(with_ellipsis(end));
+	}
+
+	match text {
+		Cow::Borrowed(text) => Cow::Borrowed(&text[..end]),
+		Cow::Owned(mut text) => {
+			text.truncate(end);
+			Cow::Owned(text)
+		}
+	}
+}
+
+/* The text as `limit` measures it */
+fn measured_form(text: &str, limit: Limit) -> Cow<'_, str> {
+	match limit {
+		Limit::Weighted(_) if !is_nfc(text) => Cow::Owned(text.nfc().collect()),
+		_ => Cow::Borrowed(text),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_truncate() {
+		assert_eq!(truncate("short", Limit::Bytes(10), Some("…")), "short");
+		assert_eq!(truncate("truncated", Limit::Bytes(5), None), "trunc");
+		assert_eq!(truncate("truncated", Limit::Bytes(5), Some("…")), "tr…");
+		assert_eq!(
+			truncate("truncated", Limit::CodePoints(5), Some("…")),
+			"trun…"
+		);
+		assert_eq!(
+			truncate("truncated", Limit::CodePoints(5), Some("...")),
+			"tr..."
+		);
+		assert_eq!(truncate("truncated", Limit::Bytes(2), Some("…")), "");
+	}
+
+	#[test]
+	fn test_truncate_keeps_characters_whole() {
+		assert_eq!(truncate("añb", Limit::Bytes(2), None), "a");
+		assert_eq!(truncate("an\u{303}b", Limit::CodePoints(2), None), "a");
+		assert_eq!(
+			truncate("an\u{303}b", Limit::Graphemes(2), None),
+			"an\u{303}"
+		);
+		assert_eq!(truncate("a💩b", Limit::Utf16(2), None), "a");
+		assert_eq!(truncate("a💩b", Limit::Utf16(3), None), "a💩");
+
+		let family = "👨\u{200D}👩\u{200D}👧";
+		let text = format!("{family}{family}");
+		assert_eq!(truncate(&text, Limit::CodePoints(7), None), family);
+		assert_eq!(truncate(&text, Limit::Bytes(24), None), family);
+	}
+
+	#[test]
+	fn test_truncate_weighted() {
+		let text = "日本語のテキスト";
+		assert_eq!(truncate(text, Limit::Weighted(7), None), "日本語");
+		assert_eq!(truncate(text, Limit::Weighted(7), Some("…")), "日本…");
+
+		let text = format!("{} https://example.com/", "a".repeat(256));
+		assert_eq!(truncate(&text, Limit::Weighted(280), None), text);
+		let text = format!("{} https://example.com/", "a".repeat(270));
+		assert_eq!(truncate(&text, Limit::Weighted(280), None), text[..271]);
+
+		let text = format!("{} https://example.com/cafe\u{301}", "a".repeat(256));
+		assert_eq!(
+			truncate(&text, Limit::Weighted(280), None),
+			text.nfc().collect::<String>()
+		);
+		let text = format!("{} https://example.com/cafe\u{301}", "e\u{301}".repeat(270));
+		assert_eq!(
+			truncate(&text, Limit::Weighted(280), Some("…")),
+			format!("{} …", "é".repeat(270))
+		);
+	}
+}
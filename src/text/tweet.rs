@@ -0,0 +1,213 @@
+use std::ops::{Range, RangeInclusive};
+
+use unicode_normalization::UnicodeNormalization;
+
+use super::graphemes;
+
+/* A range of code points that count for `weight` */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightRange {
+	pub range: RangeInclusive<char>,
+	pub weight: usize,
+}
+
+/* How tweets are measured, after twitter-text's configuration. Weights are
+ * in hundredths of a character, so a weight of 200 counts double. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+	pub max_weighted_length: usize,
+	pub scale: usize,
+	/* For code points outside of every range */
+	pub default_weight: usize,
+	pub ranges: Vec<WeightRange>,
+	/* What every URL counts as, however long it is, as it gets shortened */
+	pub url_length: usize,
+	/* Whether an emoji sequence counts as one code point of the default
+	 * weight, rather than every code point in it counting */
+	pub emoji_parsing: bool,
+}
+
+impl Default for Config {
+	/* twitter-text version 3: Latin, Cyrillic, Greek and the like count
+	 * once, most everything else including CJK counts double */
+	fn default() -> Self {
+		let range = |range, weight| WeightRange { range, weight };
+
+		Self {
+			max_weighted_length: 280,
+			scale: 100,
+			default_weight: 200,
+			ranges: vec![
+				range('\u{0}'..='\u{10FF}', 100),
+				range('\u{2000}'..='\u{200D}', 100),
+				range('\u{2010}'..='\u{201F}', 100),
+				range('\u{2032}'..='\u{2037}', 100),
+			],
+			url_length: 23,
+			emoji_parsing: true,
+		}
+	}
+}
+
+impl Config {
+	pub fn weight(&self, ch: char) -> usize {
+		self.ranges
+			.iter()
+			.find(|range| range.range.contains(&ch))
+			.map_or(self.default_weight, |range| range.weight)
+	}
+
+	/* The length of `text` in characters, rounded up. It's measured in NFC, as
+	 * the text is normalized when posted. */
+	pub fn weighted_length(&self, text: &str) -> usize {
+		self.weighted_length_nfc(&text.nfc().collect::<String>())
+	}
+
+	/* Like `weighted_length`, for text that's in NFC already */
+	pub fn weighted_length_nfc(&self, text: &str) -> usize {
+		let mut weight = 0;
+		let mut end = 0;
+
+		for url in urls(text) {
+			weight += self.weight_of_graphemes(&text[end..url.start]);
+			weight += self.url_length * self.scale;
+			end = url.end;
+		}
+		weight += self.weight_of_graphemes(&text[end..]);
+
+		weight.div_ceil(self.scale)
+	}
+
+	fn weight_of_graphemes(&self, text: &str) -> usize {
+		graphemes(text)
+			.map(|grapheme| match self.emoji_parsing && is_emoji(grapheme) {
+				true => self.default_weight,
+				false => grapheme.chars().map(|ch| self.weight(ch)).sum(),
+			})
+			.sum()
+	}
+
+	pub fn fits(&self, text: &str) -> bool {
+		self.weighted_length(text) <= self.max_weighted_length
+	}
+}
+
+/* With the default configuration */
+pub fn weighted_length(text: &str) -> usize {
+	Config::default().weighted_length(text)
+}
+
+/* With the default configuration */
+pub fn fits(text: &str) -> bool {
+	Config::default().fits(text)
+}
+
+/* Where the URLs in `text` are. They have to start a word. */
+pub fn urls(text: &str) -> Vec<Range<usize>> {
+	let mut urls = Vec::new();
+	let mut offset = 0;
+	let mut word_start = true;
+
+	while offset < text.len() {
+		let rest = &text[offset..];
+		if let Some(url) = url_at(rest).filter(|_| word_start) {
+			urls.push(offset..offset + url.len());
+			offset += url.len();
+			continue;
+		}
+
+		let grapheme = graphemes(rest).next().unwrap();
+		word_start = !grapheme.chars().any(char::is_alphanumeric);
+		offset += grapheme.len();
+	}
+
+	urls
+}
+
+/* A URL at the start of `text`. URLs need a scheme or to start with www., and
+ * trailing punctuation isn't part of them. */
+fn url_at(text: &str) -> Option<&str> {
+	let scheme = ["https://", "http://", "www."].into_iter().find(|scheme| {
+		text.get(..scheme.len())
+			.is_some_and(|start| start.eq_ignore_ascii_case(scheme))
+	})?;
+
+	let end = text.find(char::is_whitespace).unwrap_or(text.len());
+	let url = text[..end].trim_end_matches(|ch| ".,:;!?'\")]".contains(ch));
+
+	(url.len() > scheme.len()).then_some(url)
+}
+
+/* Pictographs, flags and keycaps. Text presentation of a pictograph still
+ * counts, as it's rendered as an emoji often enough. */
+fn is_emoji(grapheme: &str) -> bool {
+	let mut chars = grapheme.chars();
+	let first = chars.next().unwrap_or_default();
+
+	matches!(first, '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{2300}'..='\u{23FF}')
+		|| chars.any(|ch| ch == '\u{20E3}' || ch == '\u{FE0F}')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_weighted_length() {
+		assert_eq!(weighted_length("Hello, world"), 12);
+		assert_eq!(weighted_length("Привет"), 6);
+		assert_eq!(weighted_length("日本語"), 6);
+		assert_eq!(weighted_length("“quotes” — dash"), 15);
+		assert_eq!(weighted_length("…"), 2);
+	}
+
+	#[test]
+	fn test_emoji() {
+		assert_eq!(weighted_length("😀"), 2);
+		assert_eq!(weighted_length("👨\u{200D}👩\u{200D}👧"), 2);
+		assert_eq!(weighted_length("🇳🇱"), 2);
+		assert_eq!(weighted_length("1\u{FE0F}\u{20E3}"), 2);
+		assert_eq!(weighted_length("👍🏽!"), 3);
+
+		let config = Config {
+			emoji_parsing: false,
+			..Config::default()
+		};
+		assert_eq!(config.weighted_length("👨\u{200D}👩\u{200D}👧"), 8);
+	}
+
+	#[test]
+	fn test_urls() {
+		assert_eq!(
+			weighted_length("https://example.com/a/very/long/path/indeed"),
+			23
+		);
+		assert_eq!(weighted_length("see www.example.com."), 4 + 23 + 1);
+		assert_eq!(weighted_length("(http://x.org)"), 1 + 23 + 1);
+		assert_eq!(weighted_length("http://"), 7);
+		assert_eq!(weighted_length("nowww.example.com"), 17);
+
+		assert_eq!(urls("a http://x.org, www.y.org"), [2..14, 16..25]);
+	}
+
+	#[test]
+	fn test_nfc() {
+		assert_eq!(weighted_length("e\u{301}"), 1);
+		assert_eq!(weighted_length("\u{1100}\u{1161}"), 2);
+	}
+
+	#[test]
+	fn test_fits() {
+		assert!(fits(&"a".repeat(280)));
+		assert!(!fits(&"a".repeat(281)));
+		assert!(fits(&"日".repeat(140)));
+		assert!(!fits(&"日".repeat(141)));
+
+		let config = Config {
+			max_weighted_length: 140,
+			default_weight: 100,
+			..Config::default()
+		};
+		assert!(config.fits(&"日".repeat(140)));
+	}
+}
@@ -1,3 +1,5 @@
+use std::{borrow::Cow, fmt::Display, iter::FusedIterator, ops::Range};
+
 pub fn is_starting_byte(b: u8) -> bool {
 	b > 127 && !is_continuation_byte(b)
 }
@@ -6,12 +8,386 @@ pub fn is_continuation_byte(b: u8) -> bool {
 	(b & 0b1100_0000) == 0b1000_0000
 }
 
+/* Bytes that can never start a sequence (0xC0, 0xC1 and 0xF5 and up) have no
+ * size, even though their bit pattern looks like a lead byte. */
 pub fn sequence_size(b: u8) -> Option<u8> {
 	match b {
-		b if b & 0b1000_0000 == 0 => Some(1),
-		b if b & 0b1110_0000 == 0b1100_0000 => Some(2),
-		b if b & 0b1111_0000 == 0b1110_0000 => Some(3),
-		b if b & 0b1111_1000 == 0b1111_0000 => Some(4),
+		0x00..=0x7F => Some(1),
+		0xC2..=0xDF => Some(2),
+		0xE0..=0xEF => Some(3),
+		0xF0..=0xF4 => Some(4),
 		_ => None,
 	}
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8ErrorKind {
+	/* A code point encoded with more bytes than needed, e.g. C0 AF for '/' */
+	Overlong,
+	/* A UTF-16 surrogate (U+D800..U+DFFF), e.g. ED A0 80 */
+	Surrogate,
+	/* Above U+10FFFF, e.g. F4 90 80 80 or a lead byte of F5 and up */
+	OutOfRange,
+	/* A sequence that ends before all its continuation bytes were seen */
+	Truncated,
+	/* A continuation byte that isn't preceded by a lead byte */
+	UnexpectedContinuation,
+}
+
+impl Display for Utf8ErrorKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let description = match self {
+			Utf8ErrorKind::Overlong => "overlong encoding",
+			Utf8ErrorKind::Surrogate => "encoded surrogate",
+			Utf8ErrorKind::OutOfRange => "code point above U+10FFFF",
+			Utf8ErrorKind::Truncated => "truncated sequence",
+			Utf8ErrorKind::UnexpectedContinuation => "unexpected continuation byte",
+		};
+		write!(f, "{description}")
+	}
+}
+
+/* `len` is the length of the maximal subpart: the bytes that have to be
+ * skipped before decoding can continue. */
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Invalid UTF-8 at byte {offset}: {kind}")]
+pub struct Utf8Error {
+	pub offset: usize,
+	pub len: usize,
+	pub kind: Utf8ErrorKind,
+}
+
+/* Decodes the first character of `bytes`, returning it with its encoded
+ * length. Errors are relative to the start of `bytes`. */
+pub fn decode_char(bytes: &[u8]) -> Option<Result<(char, usize), Utf8Error>> {
+	let error = |len, kind| {
+		Err(Utf8Error {
+			offset: 0,
+			len,
+			kind,
+		})
+	};
+
+	let &lead = bytes.first()?;
+	let size = match lead {
+		0x00..=0x7F => return Some(Ok((lead as char, 1))),
+		0x80..=0xBF => return Some(error(1, Utf8ErrorKind::UnexpectedContinuation)),
+		0xC0 | 0xC1 => return Some(error(1, Utf8ErrorKind::Overlong)),
+		0xF5..=0xFF => return Some(error(1, Utf8ErrorKind::OutOfRange)),
+		_ => sequence_size(lead).unwrap() as usize,
+	};
+
+	/* The second byte has a narrower range for some lead bytes, which is what
+	 * rules out overlong forms, surrogates and code points above U+10FFFF */
+	let (second, kind) = match lead {
+		0xE0 => (0xA0..=0xBF, Utf8ErrorKind::Overlong),
+		0xED => (0x80..=0x9F, Utf8ErrorKind::Surrogate),
+		0xF0 => (0x90..=0xBF, Utf8ErrorKind::Overlong),
+		0xF4 => (0x80..=0x8F, Utf8ErrorKind::OutOfRange),
+		_ => (0x80..=0xBF, Utf8ErrorKind::Truncated),
+	};
+
+	let mut code_point = lead as u32 & (0x7F >> size);
+	for i in 1..size {
+		let Some(&b) = bytes.get(i) else {
+			return Some(error(i, Utf8ErrorKind::Truncated));
+		};
+
+		if i == 1 && is_continuation_byte(b) && !second.contains(&b) {
+			return Some(error(1, kind));
+		} else if !is_continuation_byte(b) {
+			return Some(error(i, Utf8ErrorKind::Truncated));
+		}
+
+		code_point = (code_point << 6) | (b as u32 & 0x3F);
+	}
+
+	Some(Ok((char::from_u32(code_point).unwrap(), size)))
+}
+
+/* Walks `bytes`, yielding every character with its byte offset, or an error
+ * for every invalid sequence. Decoding resumes after the maximal subpart. */
+pub fn decode(bytes: &[u8]) -> Decoder<'_> {
+	Decoder { bytes, offset: 0 }
+}
+
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+	bytes: &'a [u8],
+	offset: usize,
+}
+
+impl Iterator for Decoder<'_> {
+	type Item = Result<(usize, char), Utf8Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let offset = self.offset;
+
+		Some(match decode_char(&self.bytes[offset..])? {
+			Ok((ch, len)) => {
+				self.offset += len;
+				Ok((offset, ch))
+			}
+			Err(err) => {
+				self.offset += err.len;
+				Err(Utf8Error { offset, ..err })
+			}
+		})
+	}
+}
+
+impl FusedIterator for Decoder<'_> {}
+
+pub fn errors(bytes: &[u8]) -> impl Iterator<Item = Utf8Error> {
+	decode(bytes).filter_map(Result::err)
+}
+
+pub fn validate(bytes: &[u8]) -> Result<&str, Vec<Utf8Error>> {
+	std::str::from_utf8(bytes).map_err(|_| errors(bytes).collect())
+}
+
+pub const REPLACEMENT_CHARACTER: char = '\u{FFFD}';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lossy<'a> {
+	pub text: Cow<'a, str>,
+	/* The byte ranges that were each replaced by one U+FFFD, in order */
+	pub replaced: Vec<Range<usize>>,
+}
+
+/* Replaces every maximal subpart of an invalid sequence by U+FFFD, like the
+ * WHATWG decoder does, so e.g. a truncated 3-byte sequence becomes a single
+ * U+FFFD but a lone surrogate becomes three. */
+pub fn decode_lossy(bytes: &[u8]) -> Lossy<'_> {
+	if let Ok(text) = std::str::from_utf8(bytes) {
+		return Lossy {
+			text: Cow::Borrowed(text),
+			replaced: Vec::new(),
+		};
+	}
+
+	let mut text = String::with_capacity(bytes.len());
+	let mut replaced = Vec::new();
+
+	for result in decode(bytes) {
+		match result {
+			Ok((_, ch)) => text.push(ch),
+			Err(err) => {
+				text.push(REPLACEMENT_CHARACTER);
+				replaced.push(err.offset..(err.offset + err.len));
+			}
+		}
+	}
+
+	Lossy {
+		text: Cow::Owned(text),
+		replaced,
+	}
+}
+
+/* A byte window cut out of valid UTF-8 at arbitrary positions, so it may
+ * start and end in the middle of a sequence */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window<'a> {
+	/* Continuation bytes of a sequence that started before the window */
+	pub prefix: &'a [u8],
+	pub text: &'a str,
+	/* The start of a sequence that continues after the window */
+	pub suffix: &'a [u8],
+	/* How many bytes `suffix` still needs to be complete */
+	pub missing: usize,
+}
+
+/* Splits `bytes` into a dangling prefix, complete characters and an incomplete
+ * suffix. Fails if anything in between isn't valid UTF-8. */
+pub fn window(bytes: &[u8]) -> Result<Window<'_>, Utf8Error> {
+	let prefix_len = bytes
+		.iter()
+		.take(3)
+		.take_while(|&&b| is_continuation_byte(b))
+		.count();
+
+	/* Only a lead byte in the last 3 bytes can start an incomplete sequence */
+	let suffix_start = (bytes.len().saturating_sub(3).max(prefix_len)..bytes.len())
+		.rev()
+		.find(|&i| !is_continuation_byte(bytes[i]))
+		.filter(|&i| sequence_size(bytes[i]).is_some_and(|size| size as usize > bytes.len() - i))
+		.unwrap_or(bytes.len());
+
+	let offset_by = |offset: usize| {
+		move |err: Utf8Error| Utf8Error {
+			offset: err.offset + offset,
+			..err
+		}
+	};
+
+	let text = std::str::from_utf8(&bytes[prefix_len..suffix_start]).map_err(|_| {
+		offset_by(prefix_len)(errors(&bytes[prefix_len..suffix_start]).next().unwrap())
+	})?;
+
+	let suffix = &bytes[suffix_start..];
+	let missing = match decode_char(suffix) {
+		None => 0,
+		Some(Err(err)) if err.kind == Utf8ErrorKind::Truncated && err.len == suffix.len() => {
+			sequence_size(suffix[0]).unwrap() as usize - suffix.len()
+		}
+		Some(Err(err)) => return Err(offset_by(suffix_start)(err)),
+		Some(Ok(_)) => unreachable!("the suffix is always incomplete"),
+	};
+
+	Ok(Window {
+		prefix: &bytes[..prefix_len],
+		text,
+		suffix,
+		missing,
+	})
+}
+
+impl Window<'_> {
+	/* Checks that `next` continues exactly where this window leaves off and
+	 * returns the character split between them, if any. Error offsets are
+	 * relative to the start of this window's suffix. */
+	pub fn splice(&self, next: &Window) -> Result<Option<char>, Utf8Error> {
+		if self.suffix.is_empty() && next.prefix.is_empty() {
+			return Ok(None);
+		}
+
+		let joined = [self.suffix, next.prefix].concat();
+		match decode_char(&joined) {
+			Some(Ok((ch, len))) if len == joined.len() => Ok(Some(ch)),
+			Some(Ok((_, len))) => Err(Utf8Error {
+				offset: len,
+				len: 1,
+				kind: Utf8ErrorKind::UnexpectedContinuation,
+			}),
+			Some(Err(err)) => Err(err),
+			None => unreachable!(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn kinds(bytes: &[u8]) -> Vec<(usize, usize, Utf8ErrorKind)> {
+		errors(bytes)
+			.map(|err| (err.offset, err.len, err.kind))
+			.collect()
+	}
+
+	#[test]
+	fn test_decode_valid() {
+		let text = "aé€😀";
+		assert_eq!(
+			decode(text.as_bytes()).collect::<Result<Vec<_>, _>>(),
+			Ok(text.char_indices().collect())
+		);
+		assert_eq!(validate(text.as_bytes()), Ok(text));
+	}
+
+	#[test]
+	fn test_error_kinds() {
+		use Utf8ErrorKind::*;
+
+		assert_eq!(
+			kinds(b"\xC0\xAF"),
+			[(0, 1, Overlong), (1, 1, UnexpectedContinuation)]
+		);
+		assert_eq!(
+			kinds(b"\xE0\x80\xAF"),
+			[
+				(0, 1, Overlong),
+				(1, 1, UnexpectedContinuation),
+				(2, 1, UnexpectedContinuation),
+			]
+		);
+		assert_eq!(
+			kinds(b"a\xED\xA0\x80"),
+			[
+				(1, 1, Surrogate),
+				(2, 1, UnexpectedContinuation),
+				(3, 1, UnexpectedContinuation),
+			]
+		);
+		assert_eq!(kinds(b"\xF4\x90\x80\x80")[0], (0, 1, OutOfRange));
+		assert_eq!(kinds(b"\xF5"), [(0, 1, OutOfRange)]);
+		assert_eq!(kinds(b"\xE2\x82"), [(0, 2, Truncated)]);
+		assert_eq!(kinds(b"\xF0\x9F\x98a"), [(0, 3, Truncated)]);
+	}
+
+	#[test]
+	fn test_decode_lossy() {
+		let lossy = decode_lossy(b"a\xF1\x80\x80\xE1\x80\xC2b\x80c\x80\xBFd");
+		assert_eq!(
+			lossy.text,
+			"a\u{FFFD}\u{FFFD}\u{FFFD}b\u{FFFD}c\u{FFFD}\u{FFFD}d"
+		);
+		assert_eq!(lossy.replaced, [1..4, 4..6, 6..7, 8..9, 10..11, 11..12]);
+
+		let lossy = decode_lossy("ok".as_bytes());
+		assert!(matches!(lossy.text, Cow::Borrowed("ok")));
+		assert!(lossy.replaced.is_empty());
+	}
+
+	#[test]
+	fn test_window() {
+		let bytes = "é€😀".as_bytes();
+
+		let left = window(&bytes[..7]).unwrap();
+		assert_eq!(
+			(left.prefix, left.text, left.suffix, left.missing),
+			(&[][..], "é€", &bytes[5..7], 2)
+		);
+
+		let right = window(&bytes[7..]).unwrap();
+		assert_eq!(
+			(right.prefix, right.text, right.suffix, right.missing),
+			(&bytes[7..], "", &[][..], 0)
+		);
+
+		assert_eq!(left.splice(&right), Ok(Some('😀')));
+		assert_eq!(
+			window(&bytes[..6])
+				.unwrap()
+				.splice(&right)
+				.unwrap_err()
+				.kind,
+			Utf8ErrorKind::Truncated
+		);
+		assert_eq!(
+			window(&bytes[..7])
+				.unwrap()
+				.splice(&window(&bytes[6..]).unwrap())
+				.unwrap_err()
+				.kind,
+			Utf8ErrorKind::UnexpectedContinuation
+		);
+	}
+
+	#[test]
+	fn test_window_errors() {
+		assert_eq!(
+			window(b"\x80\x80\x80\x80a").unwrap_err(),
+			Utf8Error {
+				offset: 3,
+				len: 1,
+				kind: Utf8ErrorKind::UnexpectedContinuation
+			}
+		);
+		assert_eq!(
+			window(b"ab\xE0\x80").unwrap_err().kind,
+			Utf8ErrorKind::Overlong
+		);
+		assert_eq!(window(b"ab\xE0\x80").unwrap_err().offset, 2);
+	}
+
+	#[test]
+	fn test_sequence_size() {
+		assert_eq!(sequence_size(b'a'), Some(1));
+		assert_eq!(sequence_size(0xC3), Some(2));
+		assert_eq!(sequence_size(0xC1), None);
+		assert_eq!(sequence_size(0xF4), Some(4));
+		assert_eq!(sequence_size(0xF5), None);
+	}
+}
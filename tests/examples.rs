@@ -0,0 +1,58 @@
+use i18n_puzzles::{days, fixtures};
+
+#[test]
+fn test_examples() {
+	let fixtures = fixtures::load(&fixtures::default_dir()).unwrap();
+	let mut failures = Vec::new();
+
+	for fixture in &fixtures {
+		let Some(solver) = days::solver(fixture.day) else {
+			failures.push(format!(
+				"{}: no solver for day {}",
+				fixture.path.display(),
+				fixture.day
+			));
+			continue;
+		};
+		let Some(expected) = &fixture.answer else {
+			failures.push(format!("{}: missing answer file", fixture.path.display()));
+			continue;
+		};
+
+		match solver(&fixture.input) {
+			Ok(answer) if &answer == expected => {}
+			Ok(answer) => failures.push(format!(
+				"{}: expected {expected}, got {answer}",
+				fixture.path.display()
+			)),
+			Err(err) => failures.push(format!("{}: {err}", fixture.path.display())),
+		}
+	}
+
+	assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_every_day_has_an_example() {
+	let fixtures = fixtures::load(&fixtures::default_dir()).unwrap();
+
+	let missing = days::DAYS
+		.iter()
+		.map(|day| day.number)
+		.filter(|day| !fixtures.iter().any(|fixture| fixture.day == *day))
+		.collect::<Vec<_>>();
+
+	assert!(missing.is_empty(), "Days without examples: {missing:?}");
+}
+
+#[test]
+fn test_empty_input() {
+	for day in days::DAYS {
+		let result = std::panic::catch_unwind(|| (day.solve)(""));
+		assert!(
+			result.is_ok(),
+			"Day {:02} panics on empty input",
+			day.number
+		);
+	}
+}